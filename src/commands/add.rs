@@ -10,7 +10,7 @@ pub fn git_add(path_str: &str) -> Result<(), git2::Error> {
         println!("all added.");
     } else {
         let path = Path::new(path_str);
-        idx.add_path(&path)?;
+        idx.add_path(path)?;
         println!("{} added.", path_str);
     }
     idx.write()?;
@@ -1,39 +1,220 @@
-use git2::{BranchType, Repository};
+use crate::pretty;
+use crate::state;
+use git2::{Branch, BranchType, Commit, Config, ErrorCode, Oid, Repository};
 
-pub fn git_show_branch() -> Result<(), git2::Error> {
-    let repo = Repository::open(".")?;
-    let branches = repo.branches(Some(BranchType::Local))?;
+#[derive(Debug, Default, Clone)]
+pub struct BranchListOptions {
+    // -v: 마지막 커밋과 ahead/behind, -vv: upstream 이름까지
+    pub verbose: u8,
+    // -r: 원격 브랜치만
+    pub remotes: bool,
+    // -a: 로컬과 원격 브랜치 모두
+    pub all: bool,
+}
+
+pub fn git_show_branch(opts: &BranchListOptions) -> Result<(), git2::Error> {
+    println!("Branch 목록:");
+    for line in git_branch_list(opts)? {
+        println!("{}", line);
+    }
+    Ok(())
+}
 
+// git branch 처럼 현재 브랜치는 "* ", 나머지는 "  " 로 시작하는 줄들
+pub fn git_branch_list(opts: &BranchListOptions) -> Result<Vec<String>, git2::Error> {
+    let repo = Repository::open(".")?;
     let head = repo.head()?;
-    let head_name = head.shorthand().unwrap_or("HEAD");
 
-    println!("Branch 목록:");
-    for branch in branches {
-        let (branch, _) = branch?;
-        let name = branch.name()?.unwrap_or("unknown");
-        if name == head_name {
-            println!("* {name}");
+    let filter = if opts.all {
+        None
+    } else if opts.remotes {
+        Some(BranchType::Remote)
+    } else {
+        Some(BranchType::Local)
+    };
+
+    // (현재 브랜치 여부, 표시 이름, 브랜치)
+    let mut rows = Vec::new();
+    if !head.is_branch() && filter != Some(BranchType::Remote) {
+        let commit = head.peel_to_commit()?;
+        let name = format!("(HEAD detached at {})", &commit.id().to_string()[..7]);
+        rows.push((true, name, None));
+    }
+    for branch in repo.branches(filter)? {
+        let (branch, branch_type) = branch?;
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+        // 원격 브랜치의 HEAD(origin/HEAD) 는 심볼릭 ref 라 커밋이 없다
+        if branch_type == BranchType::Remote && branch.get().target().is_none() {
+            continue;
+        }
+        let shown = if opts.all && branch_type == BranchType::Remote {
+            format!("remotes/{}", name)
         } else {
-            println!("  {name}");
+            name.to_string()
+        };
+        rows.push((branch.is_head(), shown, Some(branch)));
+    }
+
+    let width = rows
+        .iter()
+        .map(|(_, name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut res = Vec::new();
+    for (current, name, branch) in rows {
+        let mark = if current { "* " } else { "  " };
+        if opts.verbose == 0 {
+            res.push(format!("{}{}", mark, name));
+            continue;
+        }
+
+        let commit = match &branch {
+            Some(branch) => branch.get().peel_to_commit()?,
+            None => head.peel_to_commit()?,
+        };
+        let mut line = format!(
+            "{}{:<width$} {}",
+            mark,
+            name,
+            &commit.id().to_string()[..7],
+            width = width
+        );
+        if let Some(branch) = &branch
+            && let Some(tracking) = tracking_info(&repo, branch, opts.verbose > 1)?
+        {
+            line.push_str(&format!(" [{}]", tracking));
+        }
+        line.push(' ');
+        line.push_str(&pretty::subject(&commit));
+        res.push(line);
+    }
+    Ok(res)
+}
+
+// -v 는 "ahead 1, behind 2", -vv 는 "origin/main: ahead 1, behind 2". upstream 이 사라졌으면 gone
+fn tracking_info(
+    repo: &Repository,
+    branch: &Branch,
+    with_name: bool,
+) -> Result<Option<String>, git2::Error> {
+    let Some(refname) = branch.get().name() else {
+        return Ok(None);
+    };
+    let upstream_name = match repo.branch_upstream_name(refname) {
+        Ok(name) => name,
+        Err(_) => return Ok(None),
+    };
+    let upstream_name = upstream_name.as_str().unwrap_or("");
+    let shown = upstream_name
+        .strip_prefix("refs/remotes/")
+        .or_else(|| upstream_name.strip_prefix("refs/heads/"))
+        .unwrap_or(upstream_name);
+
+    let counts = match (branch.get().target(), repo.refname_to_id(upstream_name)) {
+        (Some(local), Ok(upstream)) => {
+            let (ahead, behind) = repo.graph_ahead_behind(local, upstream)?;
+            let mut counts = Vec::new();
+            if ahead > 0 {
+                counts.push(format!("ahead {}", ahead));
+            }
+            if behind > 0 {
+                counts.push(format!("behind {}", behind));
+            }
+            counts.join(", ")
         }
+        _ => "gone".to_string(),
+    };
+
+    Ok(match (with_name, counts.is_empty()) {
+        (true, true) => Some(shown.to_string()),
+        (true, false) => Some(format!("{}: {}", shown, counts)),
+        (false, true) => None,
+        (false, false) => Some(counts),
+    })
+}
+
+// branch --set-upstream-to. branch 가 없으면 현재 브랜치
+pub fn git_set_upstream(branch_name: Option<&str>, upstream: &str) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    let name = source_branch(&repo, branch_name)?;
+    let mut branch = repo.find_branch(&name, BranchType::Local)?;
+    branch.set_upstream(Some(upstream))?;
+    println!("branch '{}' set up to track '{}'.", name, upstream);
+    Ok(())
+}
+
+// branch --unset-upstream
+pub fn git_unset_upstream(branch_name: Option<&str>) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    let name = source_branch(&repo, branch_name)?;
+    let mut branch = repo.find_branch(&name, BranchType::Local)?;
+    // upstream 브랜치가 사라졌어도 설정은 남아 있을 수 있다
+    let refname = format!("refs/heads/{}", name);
+    if repo.branch_upstream_name(&refname).is_err() {
+        return Err(git2::Error::from_str(&format!(
+            "'{}' 브랜치에 upstream 정보가 없음",
+            name
+        )));
     }
+    branch.set_upstream(None)?;
     Ok(())
 }
 
 pub fn git_create_branch(branch_name: &str) -> Result<(), git2::Error> {
-    let repo = Repository::open(".")?;
+    git_create_branch_at(branch_name, None)
+}
 
-    let head_ref = repo.head()?.resolve()?;
-    let commit = head_ref.peel_to_commit()?;
+// branch <name> [<start-point>]. 시작점이 원격 브랜치면 upstream 으로 지정한다
+pub fn git_create_branch_at(branch_name: &str, start: Option<&str>) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    let (commit, upstream) = resolve_start_point(&repo, start)?;
 
     // 브랜치 생성. force:false 인데, 이건 같은 이름으로 브랜치가 존재하는 경우 에러 발생
-    repo.branch(branch_name, &commit, false)?;
+    let mut branch = repo.branch(branch_name, &commit, false)?;
     println!("branch '{}' created", branch_name);
+
+    if let Some(upstream) = upstream {
+        branch.set_upstream(Some(&upstream))?;
+        println!("branch '{}' set up to track '{}'.", branch_name, upstream);
+    }
     Ok(())
 }
 
-pub fn git_delete_branch(branch_name: &str) -> Result<(), git2::Error> {
+// 새 브랜치의 시작 커밋과, 시작점이 원격 브랜치일 때 그 이름(upstream 으로 쓸)
+pub(crate) fn resolve_start_point<'r>(
+    repo: &'r Repository,
+    start: Option<&str>,
+) -> Result<(Commit<'r>, Option<String>), git2::Error> {
+    let Some(start) = start else {
+        return match repo.head() {
+            Ok(head) => Ok((head.peel_to_commit()?, None)),
+            Err(e) if e.code() == ErrorCode::UnbornBranch => Err(git2::Error::from_str(
+                "아직 커밋이 없어 브랜치를 만들 수 없음 (checkout --orphan <name> 사용)",
+            )),
+            Err(e) => Err(e),
+        };
+    };
+
+    let remote = repo
+        .resolve_reference_from_short_name(start)
+        .ok()
+        .filter(|reference| reference.is_remote());
+    match remote {
+        Some(reference) => {
+            let shorthand = reference.shorthand().map(str::to_string);
+            Ok((reference.peel_to_commit()?, shorthand))
+        }
+        None => Ok((repo.revparse_single(start)?.peel_to_commit()?, None)),
+    }
+}
+
+// force(-D) 가 아니면 HEAD 나 upstream 에 머지되지 않은 브랜치는 지우지 않는다
+pub fn git_delete_branch(branch_name: &str, force: bool) -> Result<(), git2::Error> {
     let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
 
     let head = repo.head()?;
     let head_name = head.shorthand().unwrap_or("HEAD");
@@ -43,17 +224,160 @@ pub fn git_delete_branch(branch_name: &str) -> Result<(), git2::Error> {
     }
 
     let mut branch = repo.find_branch(branch_name, BranchType::Local)?;
+    let tip = branch.get().target();
+
+    if !force && let Some(tip) = tip {
+        let mut bases = vec![head.peel_to_commit()?.id()];
+        if let Ok(upstream) = branch.upstream()
+            && let Some(upstream) = upstream.get().target()
+        {
+            bases.push(upstream);
+        }
+        if !is_merged(&repo, tip, &bases)? {
+            return Err(git2::Error::from_str(&format!(
+                "'{}' 브랜치는 완전히 머지되지 않았음. 그래도 지우려면 'branch -D {}'",
+                branch_name, branch_name
+            )));
+        }
+    }
     branch.delete()?;
 
-    println!("branch '{}' deleted", branch_name);
+    // 브랜치의 reflog 도 같이 지워지므로 되살릴 수 있게 마지막 커밋을 알려준다
+    match tip {
+        Some(tip) => println!(
+            "branch '{}' deleted (was {})",
+            branch_name,
+            &tip.to_string()[..7]
+        ),
+        None => println!("branch '{}' deleted", branch_name),
+    }
+    Ok(())
+}
+
+// tip 이 bases 중 하나와 같거나 그 조상이면 머지된 것
+fn is_merged(repo: &Repository, tip: Oid, bases: &[Oid]) -> Result<bool, git2::Error> {
+    for &base in bases {
+        if base == tip || repo.graph_descendant_of(base, tip)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// old 가 없으면 현재 브랜치
+fn source_branch(repo: &Repository, old: Option<&str>) -> Result<String, git2::Error> {
+    match old {
+        Some(old) => Ok(old.to_string()),
+        None => {
+            let head = repo.head()?;
+            if !head.is_branch() {
+                return Err(git2::Error::from_str(
+                    "HEAD 가 브랜치를 가리키지 않음 (detached HEAD)",
+                ));
+            }
+            Ok(head.shorthand().unwrap_or("HEAD").to_string())
+        }
+    }
+}
+
+// branch -m/-M. HEAD 가 가리키던 브랜치면 HEAD 도, branch.<old>.* 설정도 새 이름으로 옮긴다
+pub fn git_rename_branch(old: Option<&str>, new: &str, force: bool) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    let old = source_branch(&repo, old)?;
+    let mut branch = repo.find_branch(&old, BranchType::Local)?;
+    let was_head = branch.is_head();
+    if force && old != new {
+        ensure_not_current(&repo, new)?;
+    }
+
+    // libgit2 가 HEAD 와 설정 섹션을 같이 옮겨 주지만, 덮어쓰는 경우 남은 설정은 직접 정리한다
+    if force
+        && old != new
+        && let Ok(mut config) = repo.config()
+    {
+        remove_branch_config(&mut config, new)?;
+    }
+    branch.rename(new, force)?;
+
+    if was_head && repo.head()?.shorthand() != Some(new) {
+        repo.set_head(&format!("refs/heads/{}", new))?;
+    }
+    println!("branch '{}' renamed to '{}'", old, new);
+    Ok(())
+}
+
+// branch -c/-C. 같은 커밋에 새 브랜치를 만들고 branch.<old>.* 설정(upstream 등)도 복사한다
+pub fn git_copy_branch(old: Option<&str>, new: &str, force: bool) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    let old = source_branch(&repo, old)?;
+    let branch = repo.find_branch(&old, BranchType::Local)?;
+    let commit = branch.get().peel_to_commit()?;
+
+    if old == new {
+        return Err(git2::Error::from_str("같은 이름으로 복사할 수 없음"));
+    }
+    if force {
+        ensure_not_current(&repo, new)?;
+    }
+    repo.reference(
+        &format!("refs/heads/{}", new),
+        commit.id(),
+        force,
+        &format!("branch: Copied from {}", old),
+    )?;
+
+    let mut config = repo.config()?;
+    remove_branch_config(&mut config, new)?;
+    for (key, value) in branch_config(&config, &old)? {
+        config.set_str(&format!("branch.{}.{}", new, key), &value)?;
+    }
+
+    println!("branch '{}' copied to '{}'", old, new);
+    Ok(())
+}
+
+// -M/-C 로 현재 체크아웃 된 브랜치를 덮어쓰면 작업 트리와 HEAD 가 어긋난다
+fn ensure_not_current(repo: &Repository, name: &str) -> Result<(), git2::Error> {
+    if let Ok(branch) = repo.find_branch(name, BranchType::Local)
+        && branch.is_head()
+    {
+        return Err(git2::Error::from_str(
+            "현재 체크아웃 된 브랜치는 강제로 덮어쓸 수 없음",
+        ));
+    }
+    Ok(())
+}
+
+// branch.<name>.* 설정들을 (이름 뒷부분, 값) 으로
+fn branch_config(config: &Config, name: &str) -> Result<Vec<(String, String)>, git2::Error> {
+    let prefix = format!("branch.{}.", name);
+    let mut entries = Vec::new();
+    let mut iter = config.entries(Some(&format!("^{}", regex::escape(&prefix))))?;
+    while let Some(entry) = iter.next() {
+        let entry = entry?;
+        if let (Some(entry_name), Some(value)) = (entry.name(), entry.value())
+            && let Some(key) = entry_name.strip_prefix(&prefix)
+        {
+            entries.push((key.to_string(), value.to_string()));
+        }
+    }
+    Ok(entries)
+}
+
+fn remove_branch_config(config: &mut Config, name: &str) -> Result<(), git2::Error> {
+    for (key, _) in branch_config(config, name)? {
+        config.remove(&format!("branch.{}.{}", name, key))?;
+    }
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use crate::commands;
-    use crate::test_helpers::{get_repo, write_dummy_add_commit};
-    use git2::BranchType;
+    use crate::test_helpers::{get_repo, merge_history, write_dummy_add_commit};
+    use git2::{BranchType, Oid, Repository};
     use serial_test::serial;
     use uuid::Uuid;
 
@@ -69,7 +393,7 @@ mod tests {
         // show branch 를 유닛 리턴하고 해당 메서드에서 출력했던 이유는 이 방법이 현재
         // 체크아웃 된 브랜치를 마크하며 출력하기 가장 편했음.
         // 근데 테스트 하기에는 살짝쿵 애매하다.
-        assert!(commands::git_show_branch().is_ok());
+        assert!(commands::git_show_branch(&commands::BranchListOptions::default()).is_ok());
     }
 
     // FIXME branch 관련 테스트는 제일 먼저 실행되면 실패한다. 그래서 임시로 파일 생성하고 commit 하는 구간이 생겼다.
@@ -123,8 +447,246 @@ mod tests {
         assert!(repo.find_branch(&branch_name, BranchType::Local).is_ok());
 
         // 브랜치 삭제 테스트
-        assert!(commands::git_delete_branch(&branch_name).is_ok());
+        assert!(commands::git_delete_branch(&branch_name, false).is_ok());
         // 삭제 확인
         assert!(repo.find_branch(&branch_name, BranchType::Local).is_err());
     }
+
+    #[test]
+    #[serial]
+    fn test_git_delete_unmerged_branch() {
+        let repo = get_repo();
+        if repo.head().is_err() {
+            write_dummy_add_commit();
+        }
+        let branch_name = format!("unmerged_{}", Uuid::new_v4());
+
+        // HEAD 에 없는 커밋을 가진 브랜치
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = repo.signature().unwrap();
+        let unmerged = repo
+            .commit(
+                None,
+                &sig,
+                &sig,
+                "unmerged",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        repo.branch(&branch_name, &repo.find_commit(unmerged).unwrap(), false)
+            .unwrap();
+
+        let err = commands::git_delete_branch(&branch_name, false).unwrap_err();
+        assert!(
+            err.message()
+                .contains(&format!("branch -D {}", branch_name))
+        );
+        assert!(repo.find_branch(&branch_name, BranchType::Local).is_ok());
+
+        // upstream 에 머지되어 있으면 지울 수 있다
+        let upstream = format!("{}_upstream", branch_name);
+        repo.branch(&upstream, &repo.find_commit(unmerged).unwrap(), false)
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config
+            .set_str(&format!("branch.{}.remote", branch_name), ".")
+            .unwrap();
+        config
+            .set_str(
+                &format!("branch.{}.merge", branch_name),
+                &format!("refs/heads/{}", upstream),
+            )
+            .unwrap();
+        assert!(commands::git_delete_branch(&branch_name, false).is_ok());
+
+        // -D 는 머지 여부와 상관없이 지운다
+        assert!(commands::git_delete_branch(&upstream, true).is_ok());
+        assert!(repo.find_branch(&upstream, BranchType::Local).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_rename_and_copy_branch() {
+        let repo = get_repo();
+        if repo.head().is_err() {
+            write_dummy_add_commit();
+        }
+        let original = repo.head().unwrap().shorthand().unwrap().to_string();
+        let renamed = format!("renamed_{}", Uuid::new_v4());
+        let copied = format!("copied_{}", Uuid::new_v4());
+        repo.config()
+            .unwrap()
+            .set_str(&format!("branch.{}.description", original), "main line")
+            .unwrap();
+
+        // 현재 브랜치 이름을 바꾸면 HEAD 와 설정도 따라간다
+        commands::git_rename_branch(None, &renamed, false).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some(renamed.as_str()));
+        assert!(repo.find_branch(&original, BranchType::Local).is_err());
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config
+                .get_str(&format!("branch.{}.description", renamed))
+                .unwrap(),
+            "main line"
+        );
+
+        // 복사는 커밋과 설정을 그대로 가져가고 HEAD 는 그대로
+        commands::git_copy_branch(Some(&renamed), &copied, false).unwrap();
+        let head = repo.head().unwrap();
+        assert_eq!(head.shorthand(), Some(renamed.as_str()));
+        let copy = repo.find_branch(&copied, BranchType::Local).unwrap();
+        assert_eq!(copy.get().target(), head.target());
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config
+                .get_str(&format!("branch.{}.description", copied))
+                .unwrap(),
+            "main line"
+        );
+
+        // 현재 브랜치는 -M/-C 로도 덮어쓸 수 없다
+        assert!(commands::git_rename_branch(Some(&copied), &renamed, true).is_err());
+        assert!(commands::git_copy_branch(Some(&copied), &renamed, true).is_err());
+        assert!(repo.find_branch(&copied, BranchType::Local).is_ok());
+        assert_eq!(repo.head().unwrap().shorthand(), Some(renamed.as_str()));
+
+        // 이미 있는 이름은 -M 일 때만
+        let other = format!("other_{}", Uuid::new_v4());
+        commands::git_create_branch(&other).unwrap();
+        assert!(commands::git_rename_branch(Some(&copied), &other, false).is_err());
+        commands::git_rename_branch(Some(&copied), &other, true).unwrap();
+        assert!(repo.find_branch(&copied, BranchType::Local).is_err());
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert!(
+            config
+                .get_str(&format!("branch.{}.description", copied))
+                .is_err()
+        );
+        assert_eq!(
+            config
+                .get_str(&format!("branch.{}.description", other))
+                .unwrap(),
+            "main line"
+        );
+
+        // clean up
+        commands::git_delete_branch(&other, true).unwrap();
+        commands::git_rename_branch(None, &original, false).unwrap();
+        get_repo()
+            .config()
+            .unwrap()
+            .remove(&format!("branch.{}.description", original))
+            .unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_branch_upstream() {
+        let (_dir, [root, side, main, _merge]) = merge_history();
+        let repo = Repository::open(".").unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", main, false, "fetch")
+            .unwrap();
+        repo.reference("refs/remotes/origin/side", root, false, "fetch")
+            .unwrap();
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/main",
+            false,
+            "clone",
+        )
+        .unwrap();
+
+        commands::git_set_upstream(None, "origin/main").unwrap();
+        commands::git_set_upstream(Some("side"), "origin/side").unwrap();
+
+        let list = |verbose, remotes, all| {
+            commands::git_branch_list(&commands::BranchListOptions {
+                verbose,
+                remotes,
+                all,
+            })
+            .unwrap()
+        };
+        assert_eq!(list(0, false, false), ["* main", "  side"]);
+        assert_eq!(list(0, true, false), ["  origin/main", "  origin/side"]);
+        assert_eq!(
+            list(0, false, true),
+            [
+                "* main",
+                "  side",
+                "  remotes/origin/main",
+                "  remotes/origin/side"
+            ]
+        );
+
+        let short = |oid: Oid| oid.to_string()[..7].to_string();
+        let merge = repo.head().unwrap().target().unwrap();
+        assert_eq!(
+            list(1, false, false),
+            [
+                format!("* main {} [ahead 2] merge", short(merge)),
+                format!("  side {} [ahead 1] side", short(side)),
+            ]
+        );
+        assert_eq!(
+            list(2, false, false)[0],
+            format!("* main {} [origin/main: ahead 2] merge", short(merge))
+        );
+
+        // upstream 브랜치가 사라지면 gone
+        repo.find_reference("refs/remotes/origin/side")
+            .unwrap()
+            .delete()
+            .unwrap();
+        assert_eq!(
+            list(2, false, false)[1],
+            format!("  side {} [origin/side: gone] side", short(side))
+        );
+
+        commands::git_unset_upstream(Some("side")).unwrap();
+        assert!(commands::git_unset_upstream(Some("side")).is_err());
+        assert_eq!(
+            list(2, false, false)[1],
+            format!("  side {} side", short(side))
+        );
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_create_branch_at() {
+        let (_dir, [root, side, _main, _merge]) = merge_history();
+        let repo = Repository::open(".").unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/side", side, false, "fetch")
+            .unwrap();
+
+        commands::git_create_branch_at("from_tag", Some("v1~1")).unwrap();
+        let branch = repo.find_branch("from_tag", BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(root));
+        assert!(branch.upstream().is_err());
+
+        // 원격 브랜치에서 만들면 upstream 이 자동으로 지정된다
+        commands::git_create_branch_at("tracking", Some("origin/side")).unwrap();
+        let branch = repo.find_branch("tracking", BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(side));
+        assert_eq!(
+            branch.upstream().unwrap().name().unwrap(),
+            Some("origin/side")
+        );
+
+        assert!(commands::git_create_branch_at("nowhere", Some("no-such-rev")).is_err());
+
+        // 커밋이 없는 저장소에서는 만들 수 없다
+        let empty = tempfile::TempDir::new().unwrap();
+        Repository::init(empty.path()).unwrap();
+        std::env::set_current_dir(empty.path()).unwrap();
+        assert!(commands::git_create_branch("topic").is_err());
+        get_repo();
+    }
 }
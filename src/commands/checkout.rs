@@ -1,35 +1,404 @@
+use crate::commands::branch;
+use crate::hooks;
+use crate::pretty;
+use crate::state;
 use git2::build::CheckoutBuilder;
-use git2::Repository;
+use git2::{CheckoutNotificationType, Commit, ErrorCode, Object, Oid, Repository, Tree};
+use std::cell::RefCell;
+use std::path::Path;
+
+#[derive(Debug, Default, Clone)]
+pub struct CheckoutOptions {
+    // --force: 로컬 변경 사항을 버리고 체크아웃
+    pub force: bool,
+    // --merge: 로컬 변경 사항을 3-way 머지로 새 브랜치에 옮긴다
+    pub merge: bool,
+}
 
 pub fn git_checkout(branch: &str) -> Result<(), git2::Error> {
-    let repo = Repository::open(".")?;
+    git_checkout_with(branch, &CheckoutOptions::default())
+}
 
-    let (object, reference) = repo.revparse_ext(branch)?;
+// 기본은 safe 체크아웃. 로컬 변경 사항을 덮어써야 하면 해당 경로들을 알려주고 멈춘다.
+// "-" 는 직전 브랜치, 로컬에 없고 원격 하나에만 있는 브랜치는 추적 브랜치를 만들어 이동한다
+pub fn git_checkout_with(target: &str, opts: &CheckoutOptions) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
 
-    let mut checkout_builder = CheckoutBuilder::new();
-    checkout_builder.force();
+    // libgit2 는 HEAD reflog 의 "checkout: moving from A to B" 로 @{-1} 을 찾는다
+    let rev = if target == "-" { "@{-1}" } else { target };
+    let (object, reference) = match repo.revparse_ext(rev) {
+        Ok(resolved) => resolved,
+        Err(e) => match remote_branch_for(&repo, target)? {
+            Some(remote_branch) => {
+                return git_checkout_new_branch(target, Some(&remote_branch), opts);
+            }
+            None if target == "-" => {
+                return Err(git2::Error::from_str("이전에 체크아웃한 브랜치가 없음"));
+            }
+            None => return Err(e),
+        },
+    };
+    let commit = object.peel_to_commit()?;
+    let prev = PrevHead::read(&repo);
 
-    repo.checkout_tree(&object, Some(&mut checkout_builder))?;
+    checkout_tree(&repo, &object, opts)?;
 
-    match reference {
+    // 로컬 브랜치가 아닌 ref(원격 브랜치, 태그)나 커밋이면 detached HEAD 가 된다
+    match reference.filter(|reference| reference.is_branch()) {
+        Some(reference) => {
+            let name = reference
+                .name()
+                .ok_or_else(|| git2::Error::from_str("유효하지 않은 레퍼런스 이름"))?;
+            repo.set_head(name)?;
+            let shorthand = reference.shorthand().unwrap_or(name);
+            if prev.branch.as_deref() == Some(name) {
+                println!("Already on '{}'", shorthand);
+            } else {
+                println!("Switched to branch '{}'", shorthand);
+            }
+        }
         None => {
-            repo.set_head_detached(object.id())?;
+            repo.set_head_detached(commit.id())?;
+            print_detached_notice(target, &commit);
         }
-        Some(ref_ref) => {
-            if let Some(name) = ref_ref.name() {
-                repo.set_head(name)?;
-            } else {
-                return Err(git2::Error::from_str("유효하지 않은 레퍼런스 이름"));
+    }
+    prev.warn_left_behind(&repo)?;
+
+    let _ = hooks::run_hook(
+        &repo,
+        "post-checkout",
+        &[&prev.oid.to_string(), &commit.id().to_string(), "1"],
+        None,
+    );
+    Ok(())
+}
+
+// checkout -b / switch -c. 시작점(없으면 HEAD)에 새 브랜치를 만들고 이동한다
+pub fn git_checkout_new_branch(
+    branch_name: &str,
+    start: Option<&str>,
+    opts: &CheckoutOptions,
+) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+
+    if repo
+        .find_branch(branch_name, git2::BranchType::Local)
+        .is_ok()
+    {
+        return Err(git2::Error::from_str(&format!(
+            "'{}' 브랜치가 이미 존재함",
+            branch_name
+        )));
+    }
+    let (commit, upstream) = branch::resolve_start_point(&repo, start)?;
+    let prev = PrevHead::read(&repo);
+
+    // 체크아웃이 실패하면 브랜치도 만들지 않는다
+    checkout_tree(&repo, commit.as_object(), opts)?;
+    let mut new_branch = repo.branch(branch_name, &commit, false)?;
+    if let Some(upstream) = upstream {
+        new_branch.set_upstream(Some(&upstream))?;
+        println!("branch '{}' set up to track '{}'.", branch_name, upstream);
+    }
+    repo.set_head(&format!("refs/heads/{}", branch_name))?;
+    println!("Switched to a new branch '{}'", branch_name);
+    prev.warn_left_behind(&repo)?;
+
+    let _ = hooks::run_hook(
+        &repo,
+        "post-checkout",
+        &[&prev.oid.to_string(), &commit.id().to_string(), "1"],
+        None,
+    );
+    Ok(())
+}
+
+// switch 는 브랜치로만 이동한다. 커밋으로 이동하려면 --detach
+pub fn git_switch(target: &str, detach: bool, opts: &CheckoutOptions) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    if detach {
+        let commit = repo.revparse_single(target)?.peel_to_commit()?;
+        return git_checkout_with(&commit.id().to_string(), opts);
+    }
+    if target != "-"
+        && repo.find_branch(target, git2::BranchType::Local).is_err()
+        && remote_branch_for(&repo, target)?.is_none()
+    {
+        return Err(git2::Error::from_str(&format!(
+            "'{}' 는 브랜치가 아님 (커밋으로 이동하려면 --detach)",
+            target
+        )));
+    }
+    git_checkout_with(target, opts)
+}
+
+// 로컬 브랜치는 없고 원격 하나에만 같은 이름의 브랜치가 있으면 "<remote>/<name>"
+fn remote_branch_for(repo: &Repository, name: &str) -> Result<Option<String>, git2::Error> {
+    if repo.find_branch(name, git2::BranchType::Local).is_ok() {
+        return Ok(None);
+    }
+    let mut found = Vec::new();
+    for remote in repo.remotes()?.iter().flatten() {
+        if repo
+            .find_reference(&format!("refs/remotes/{}/{}", remote, name))
+            .is_ok()
+        {
+            found.push(format!("{}/{}", remote, name));
+        }
+    }
+    if found.len() > 1 {
+        return Err(git2::Error::from_str(&format!(
+            "'{}' 가 여러 원격에 있음 ({}). checkout -b {} <remote>/{} 로 지정하세요",
+            name,
+            found.join(", "),
+            name,
+            name
+        )));
+    }
+    Ok(found.pop())
+}
+
+fn print_detached_notice(target: &str, commit: &Commit) {
+    let short = &commit.id().to_string()[..7];
+    println!(
+        "'{}' 로 이동함. 브랜치가 아닌 커밋을 가리키는 'detached HEAD' 상태임.",
+        target
+    );
+    println!(
+        "여기서 만든 커밋은 다른 곳으로 이동하면 어떤 브랜치에도 남지 않으니, 남기려면 새 브랜치를 만드세요:"
+    );
+    println!("  switch -c <new-branch>");
+    println!("HEAD is now at {} {}", short, pretty::subject(commit));
+}
+
+// 이동하기 전의 HEAD
+struct PrevHead {
+    oid: Oid,
+    // HEAD 가 가리키던 브랜치 ref 이름. detached 나 unborn 이면 None
+    branch: Option<String>,
+}
+
+impl PrevHead {
+    fn read(repo: &Repository) -> PrevHead {
+        let oid = repo.refname_to_id("HEAD").unwrap_or(Oid::zero());
+        let branch = repo
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(str::to_string));
+        PrevHead { oid, branch }
+    }
+
+    // detached HEAD 에서 만든 커밋을 어떤 ref 에서도 닿지 않게 두고 떠나면 알려준다
+    fn warn_left_behind(&self, repo: &Repository) -> Result<(), git2::Error> {
+        if self.branch.is_some() || self.oid.is_zero() {
+            return Ok(());
+        }
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(self.oid)?;
+        revwalk.hide_head()?;
+        revwalk.hide_glob("refs/*")?;
+        let left: Vec<Oid> = revwalk.collect::<Result<_, _>>()?;
+        if left.is_empty() {
+            return Ok(());
+        }
+
+        println!(
+            "경고: 어떤 브랜치에도 연결되지 않은 커밋 {}개를 두고 떠남:",
+            left.len()
+        );
+        for oid in left.iter().take(5) {
+            let commit = repo.find_commit(*oid)?;
+            println!("  {} {}", &oid.to_string()[..7], pretty::subject(&commit));
+        }
+        if left.len() > 5 {
+            println!("  ... 외 {}개", left.len() - 5);
+        }
+        println!(
+            "남기려면 지금 브랜치를 만드세요: branch <new-branch> {}",
+            &self.oid.to_string()[..7]
+        );
+        Ok(())
+    }
+}
+
+pub(crate) fn checkout_tree(
+    repo: &Repository,
+    object: &Object,
+    opts: &CheckoutOptions,
+) -> Result<(), git2::Error> {
+    if opts.force {
+        let mut checkout_builder = CheckoutBuilder::new();
+        checkout_builder.force();
+        return repo.checkout_tree(object, Some(&mut checkout_builder));
+    }
+
+    let conflicts = RefCell::new(Vec::new());
+    let res = {
+        let mut checkout_builder = CheckoutBuilder::new();
+        checkout_builder
+            .safe()
+            .notify_on(CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path {
+                    conflicts.borrow_mut().push(path.display().to_string());
+                }
+                true
+            });
+        repo.checkout_tree(object, Some(&mut checkout_builder))
+    };
+    match res {
+        Err(e) if e.code() == ErrorCode::Conflict && opts.merge => {
+            // 추적하지 않는 파일은 머지할 기준이 없으므로 덮어쓰지 않고 중단한다
+            let untracked = untracked_paths(repo, &conflicts.into_inner())?;
+            if !untracked.is_empty() {
+                return Err(git2::Error::from_str(&format!(
+                    "체크아웃하면 다음 추적하지 않는 파일이 덮어써짐:\n{}\n옮기거나 지운 뒤 다시 시도하세요",
+                    tab_list(&untracked)
+                )));
             }
+            checkout_merge(repo, &object.peel_to_tree()?)
         }
+        Err(e) if e.code() == ErrorCode::Conflict => Err(git2::Error::from_str(&format!(
+            "체크아웃하면 다음 파일의 로컬 변경 사항이 덮어써짐:\n{}\n커밋하거나, 버리려면 --force, 옮기려면 --merge",
+            tab_list(&conflicts.into_inner())
+        ))),
+        res => res,
     }
+}
+
+fn tab_list(paths: &[String]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("\t{}", path))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// HEAD 에도 인덱스에도 없는 경로
+fn untracked_paths(repo: &Repository, paths: &[String]) -> Result<Vec<String>, git2::Error> {
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let index = repo.index()?;
+    Ok(paths
+        .iter()
+        .filter(|path| {
+            let path = Path::new(path.as_str());
+            index.get_path(path, 0).is_none()
+                && head_tree
+                    .as_ref()
+                    .is_none_or(|tree| tree.get_path(path).is_err())
+        })
+        .cloned()
+        .collect())
+}
+
+// checkout -m. HEAD 를 공통 조상으로, 대상 트리와 로컬 변경 사항(추적 중인 파일)을 3-way 머지한다.
+// 인덱스는 대상 트리와 같아지고 머지 결과는 작업 트리에 남는다. 충돌한 파일은 인덱스에 충돌로 기록한다
+fn checkout_merge(repo: &Repository, target: &Tree) -> Result<(), git2::Error> {
+    let head_tree = repo.head()?.peel_to_tree()?;
+
+    let mut index = repo.index()?;
+    index.update_all(["*"].iter(), None)?;
+    let local_tree = repo.find_tree(index.write_tree()?)?;
+    // 메모리에서 바꾼 인덱스를 디스크 내용으로 되돌린다
+    index.read(true)?;
+
+    let mut merged = repo.merge_trees(&head_tree, target, &local_tree, None)?;
+
+    let mut checkout_builder = CheckoutBuilder::new();
+    checkout_builder.force();
+    repo.checkout_tree(target.as_object(), Some(&mut checkout_builder))?;
+
+    // 로컬에서 지운 파일은 머지 결과에도 없으므로 작업 트리에서도 지운다
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("bare 저장소에서는 체크아웃할 수 없음"))?;
+    for entry in index.iter() {
+        let path = String::from_utf8_lossy(&entry.path).to_string();
+        if merged.get_path(Path::new(&path), 0).is_none()
+            && merged.conflict_get(Path::new(&path)).is_err()
+        {
+            let _ = std::fs::remove_file(workdir.join(&path));
+        }
+    }
+
+    let mut checkout_builder = CheckoutBuilder::new();
+    checkout_builder
+        .force()
+        .allow_conflicts(true)
+        .conflict_style_merge(true)
+        .update_index(false);
+    repo.checkout_index(Some(&mut merged), Some(&mut checkout_builder))?;
+
+    let mut conflicted = Vec::new();
+    for conflict in merged.conflicts()? {
+        let conflict = conflict?;
+        let Some(any) = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+        else {
+            continue;
+        };
+        let path = String::from_utf8_lossy(&any.path).to_string();
+        let _ = index.remove(Path::new(&path), 0);
+        for entry in [conflict.ancestor, conflict.our, conflict.their]
+            .into_iter()
+            .flatten()
+        {
+            index.add(&entry)?;
+        }
+        conflicted.push(path);
+    }
+    index.write()?;
+
+    for path in conflicted {
+        println!("충돌: {}", path);
+    }
+    Ok(())
+}
+
+// checkout --orphan. 커밋이 없는 새 브랜치로 HEAD 를 옮긴다. 인덱스와 작업 트리는 그대로 둔다
+pub fn git_checkout_orphan(branch: &str) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+
+    let refname = format!("refs/heads/{}", branch);
+    if !git2::Reference::is_valid_name(&refname) {
+        return Err(git2::Error::from_str(&format!(
+            "유효하지 않은 브랜치 이름: {}",
+            branch
+        )));
+    }
+    if repo.find_reference(&refname).is_ok() {
+        return Err(git2::Error::from_str(&format!(
+            "'{}' 브랜치가 이미 존재함",
+            branch
+        )));
+    }
+
+    let prev_head = repo.refname_to_id("HEAD").unwrap_or(Oid::zero());
+    repo.set_head(&refname)?;
+    println!("Switched to a new branch '{}'", branch);
+
+    let _ = hooks::run_hook(
+        &repo,
+        "post-checkout",
+        &[&prev_head.to_string(), &Oid::zero().to_string(), "1"],
+        None,
+    );
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::commands::git_checkout;
-    use crate::test_helpers::{get_repo, write_dummy_add_commit};
+    use super::*;
+    use crate::commands;
+    use crate::commands::{git_checkout, git_checkout_orphan};
+    use crate::test_helpers::{get_repo, merge_history, write_dummy_add_commit};
     use serial_test::serial;
 
     #[test]
@@ -56,4 +425,191 @@ mod tests {
         let head_name = head_ref.shorthand().unwrap_or("");
         assert_eq!(head_name, branch_name, "체크아웃된 브랜치가 다릅니다");
     }
+
+    #[test]
+    #[serial]
+    fn test_git_checkout_orphan() {
+        let (_dir, _) = merge_history();
+        let repo = git2::Repository::open(".").unwrap();
+        std::fs::write("staged.txt", "keep me").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+
+        git_checkout_orphan("fresh").expect("orphan 체크아웃 실패");
+        assert!(repo.head().is_err());
+        assert!(repo.head_detached().is_ok_and(|detached| !detached));
+        assert_eq!(
+            repo.find_reference("HEAD").unwrap().symbolic_target(),
+            Some("refs/heads/fresh")
+        );
+        // 인덱스는 그대로
+        assert!(
+            repo.index()
+                .unwrap()
+                .get_path(std::path::Path::new("staged.txt"), 0)
+                .is_some()
+        );
+
+        assert!(git_checkout_orphan("main").is_err());
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_checkout_local_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        let write = |content: &str| std::fs::write("a.txt", content).unwrap();
+        let read = || std::fs::read_to_string("a.txt").unwrap();
+
+        write("1\n2\n3\n4\n5\n");
+        commands::git_add("a.txt").unwrap();
+        commands::git_commit("base").unwrap();
+        git_checkout_new_branch("other", None, &CheckoutOptions::default()).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        write("1\n2\n3\n4\nfive\n");
+        commands::git_add("a.txt").unwrap();
+        commands::git_commit("other").unwrap();
+        git_checkout("main").unwrap();
+
+        // 로컬 변경 사항을 덮어쓰는 체크아웃은 거부한다
+        write("one\n2\n3\n4\n5\n");
+        let err = git_checkout("other").unwrap_err();
+        assert!(err.message().contains("\ta.txt"));
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        assert_eq!(read(), "one\n2\n3\n4\n5\n");
+
+        // --merge 는 로컬 변경 사항을 옮겨 온다 (스테이지는 하지 않는다)
+        let merge = CheckoutOptions {
+            merge: true,
+            ..Default::default()
+        };
+        git_checkout_with("other", &merge).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        assert_eq!(read(), "one\n2\n3\n4\nfive\n");
+        let status = repo.status_file(Path::new("a.txt")).unwrap();
+        assert_eq!(status, git2::Status::WT_MODIFIED);
+
+        // 같은 줄을 바꿨으면 충돌로 남는다
+        write("one\n2\n3\n4\nFIVE\n");
+        git_checkout_with("main", &merge).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        assert!(read().contains("<<<<<<<"));
+        let has_conflicts = || {
+            let mut index = repo.index().unwrap();
+            index.read(false).unwrap();
+            index.has_conflicts()
+        };
+        assert!(has_conflicts());
+
+        // --force 는 로컬 변경 사항을 버린다
+        let force = CheckoutOptions {
+            force: true,
+            ..Default::default()
+        };
+        git_checkout_with("other", &force).unwrap();
+        assert_eq!(read(), "1\n2\n3\n4\nfive\n");
+        assert!(!has_conflicts());
+
+        // switch 는 --detach 없이 커밋으로 이동하지 않는다
+        assert!(git_switch("main~0", false, &CheckoutOptions::default()).is_err());
+        git_switch("main~0", true, &CheckoutOptions::default()).unwrap();
+        assert!(repo.head_detached().unwrap());
+        assert!(git_checkout_new_branch("main", None, &CheckoutOptions::default()).is_err());
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_checkout_merge_keeps_untracked() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        std::fs::write("a.txt", "a").unwrap();
+        commands::git_add("a.txt").unwrap();
+        commands::git_commit("a").unwrap();
+        git_checkout_new_branch("other", None, &CheckoutOptions::default()).unwrap();
+        std::fs::write("u.txt", "tracked").unwrap();
+        commands::git_add("u.txt").unwrap();
+        commands::git_commit("u").unwrap();
+        git_checkout("main").unwrap();
+
+        // 다른 브랜치가 추적하는 파일과 같은 이름의 추적하지 않는 파일
+        std::fs::write("u.txt", "precious").unwrap();
+        std::fs::write("a.txt", "local").unwrap();
+        assert!(git_checkout("other").is_err());
+        let merge = CheckoutOptions {
+            merge: true,
+            ..Default::default()
+        };
+        let err = git_checkout_with("other", &merge).unwrap_err();
+        assert!(err.message().contains("\tu.txt"));
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        assert_eq!(std::fs::read_to_string("u.txt").unwrap(), "precious");
+        assert_eq!(std::fs::read_to_string("a.txt").unwrap(), "local");
+
+        // 추적하지 않는 파일을 치우면 추적 중인 로컬 변경 사항만 옮긴다
+        std::fs::remove_file("u.txt").unwrap();
+        git_checkout_with("other", &merge).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        assert_eq!(std::fs::read_to_string("u.txt").unwrap(), "tracked");
+        assert_eq!(std::fs::read_to_string("a.txt").unwrap(), "local");
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_checkout_remote_and_previous() {
+        let (_dir, [root, side, _main, merge]) = merge_history();
+        let repo = Repository::open(".").unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/feature", side, false, "fetch")
+            .unwrap();
+        let head_name = || {
+            repo.find_reference("HEAD")
+                .unwrap()
+                .symbolic_target()
+                .map(str::to_string)
+        };
+
+        // 원격에만 있는 브랜치는 추적 브랜치를 만들어 이동한다
+        git_checkout("feature").unwrap();
+        assert_eq!(head_name().as_deref(), Some("refs/heads/feature"));
+        let feature = repo
+            .find_branch("feature", git2::BranchType::Local)
+            .unwrap();
+        assert_eq!(feature.get().target(), Some(side));
+        assert_eq!(
+            feature.upstream().unwrap().name().unwrap(),
+            Some("origin/feature")
+        );
+
+        // "-" 는 직전 브랜치
+        git_checkout("-").unwrap();
+        assert_eq!(head_name().as_deref(), Some("refs/heads/main"));
+        git_switch("-", false, &CheckoutOptions::default()).unwrap();
+        assert_eq!(head_name().as_deref(), Some("refs/heads/feature"));
+
+        // 원격 브랜치나 커밋은 detached HEAD
+        git_checkout("origin/feature").unwrap();
+        assert!(repo.head_detached().unwrap());
+        assert_eq!(repo.head().unwrap().target(), Some(side));
+        git_checkout(&root.to_string()).unwrap();
+        assert!(repo.head_detached().unwrap());
+
+        // detached HEAD 에서 만든 커밋을 두고 떠나도 이동은 된다
+        let sig = repo.signature().unwrap();
+        let tree = repo.find_commit(root).unwrap().tree().unwrap();
+        let parent = repo.find_commit(root).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "detached", &tree, &[&parent])
+            .unwrap();
+        git_checkout("main").unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(merge));
+        get_repo();
+    }
 }
@@ -1,32 +1,269 @@
-use git2::Repository;
+use crate::commands::lint;
+use crate::hooks;
+use crate::identity::{self, IdentityOverride};
+use crate::message::{self, Cleanup};
+use crate::signing;
+use crate::state;
+use git2::{Commit, Error, Oid, Repository, Signature, Tree};
+use std::fs;
+use std::io::{self, Read};
 
-pub fn git_commit(message: &str) -> Result<String, git2::Error> {
+#[derive(Debug, Default, Clone)]
+pub struct CommitOptions {
+    // -m 로 받은 문단들. 여러 개면 빈 줄로 이어붙인다.
+    pub messages: Vec<String>,
+    // -F <file>, "-" 이면 stdin
+    pub file: Option<String>,
+    // -m/-F 가 있어도 에디터를 연다
+    pub edit: bool,
+    pub cleanup: Option<Cleanup>,
+    // --author, --date
+    pub identity: IdentityOverride,
+    // --no-verify: pre-commit, commit-msg 훅과 메시지 lint 를 건너뛴다
+    pub no_verify: bool,
+    // --trailer "Key: value"
+    pub trailers: Vec<(String, String)>,
+    // -s, --signoff: Signed-off-by trailer 추가
+    pub signoff: bool,
+    // --amend: HEAD 커밋을 새 커밋으로 대체
+    pub amend: bool,
+    // --no-edit: --amend 시 기존 메시지를 에디터 없이 그대로 사용
+    pub no_edit: bool,
+    // -S / --no-gpg-sign, 없으면 commit.gpgSign 설정을 따른다
+    pub sign: Option<bool>,
+}
+
+pub fn git_commit(message: &str) -> Result<String, Error> {
+    git_commit_with(&CommitOptions {
+        messages: vec![message.to_string()],
+        ..Default::default()
+    })
+}
+
+pub fn git_commit_with(opts: &CommitOptions) -> Result<String, Error> {
     let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    if !opts.no_verify {
+        hooks::run_hook(&repo, "pre-commit", &[], None)?;
+    }
+    let amend_base = match opts.amend {
+        true => Some(
+            repo.head()
+                .and_then(|head| head.peel_to_commit())
+                .map_err(|_| Error::from_str("amend 할 커밋이 없음"))?,
+        ),
+        false => None,
+    };
+
+    let mut message = prepare_message(&repo, opts, amend_base.as_ref())?;
+    let mut trailers = opts.trailers.clone();
+    if opts.signoff {
+        trailers.push(message::signoff_trailer(&repo)?);
+    }
+    if !trailers.is_empty() {
+        message = message::append_trailers(&message, &trailers);
+    }
+    if !opts.no_verify {
+        lint::check_commit_message(&repo, &message)?;
+    }
     let mut idx = repo.index()?;
 
     let tree_id = idx.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
-    let sig = repo.signature()?;
+    let committer = identity::committer_signature(&repo)?;
+
+    let sign = signing::should_sign(&repo, opts.sign, "commit.gpgSign");
+    let summary = message.lines().next().unwrap_or("").to_string();
 
-    let parent_commits = match repo.head() {
-        Ok(head_ref) => {
-            let head = head_ref
-                .target()
-                .ok_or_else(|| git2::Error::from_str("HEAD refers to non-HEAD"))?;
-            vec![repo.find_commit(head)?]
+    let commit_oid = match amend_base {
+        Some(base) => {
+            // --author, --date 가 없으면 원래 작성자를 유지한다
+            let author = match (&opts.identity.author, &opts.identity.date) {
+                (None, None) => base.author().to_owned(),
+                _ => identity::author_signature(&repo, &opts.identity)?,
+            };
+            let parents: Vec<Commit> = base.parents().collect();
+            let parents: Vec<&Commit> = parents.iter().collect();
+            let oid = create_commit(&repo, &author, &committer, &message, &tree, &parents, sign)?;
+            update_head(&repo, oid, &format!("commit (amend): {}", summary))?;
+            oid
         }
-        Err(_) => Vec::new(),
-    };
+        None => {
+            let author = identity::author_signature(&repo, &opts.identity)?;
+            let parent_commits = match repo.head() {
+                Ok(head_ref) => {
+                    let head = head_ref
+                        .target()
+                        .ok_or_else(|| git2::Error::from_str("HEAD refers to non-HEAD"))?;
+                    vec![repo.find_commit(head)?]
+                }
+                Err(_) => Vec::new(),
+            };
 
-    let parents: Vec<&git2::Commit> = parent_commits.iter().collect();
+            let parents: Vec<&git2::Commit> = parent_commits.iter().collect();
 
-    let commit_oid = repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)?;
+            let oid = create_commit(&repo, &author, &committer, &message, &tree, &parents, sign)?;
+            let kind = if parents.is_empty() {
+                "commit (initial)"
+            } else {
+                "commit"
+            };
+            update_head(&repo, oid, &format!("{}: {}", kind, summary))?;
+            oid
+        }
+    };
     println!("commit created: {}", commit_oid);
+
+    // post-commit 결과는 커밋에 영향을 주지 않는다
+    let _ = hooks::run_hook(&repo, "post-commit", &[], None);
     Ok(commit_oid.to_string())
 }
 
+// 서명이 필요하면 commit_create_buffer 로 만든 내용에 서명을 붙여 저장한다
+pub(crate) fn create_commit(
+    repo: &Repository,
+    author: &Signature,
+    committer: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+    sign: bool,
+) -> Result<Oid, Error> {
+    if !sign {
+        return repo.commit(None, author, committer, message, tree, parents);
+    }
+    let buf = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let content = buf
+        .as_str()
+        .ok_or_else(|| Error::from_str("커밋 내용이 UTF-8 이 아님"))?;
+    let signature = signing::signer(repo)?.sign(content.as_bytes())?;
+    repo.commit_signed(content, &signature, None)
+}
+
+// HEAD 가 가리키는 브랜치(또는 detached HEAD)를 새 커밋으로 옮긴다
+pub(crate) fn update_head(repo: &Repository, oid: Oid, reflog_msg: &str) -> Result<(), Error> {
+    let head = repo.find_reference("HEAD")?;
+    match head.symbolic_target() {
+        Some(target) => repo.reference(target, oid, true, reflog_msg)?,
+        None => repo.reference("HEAD", oid, true, reflog_msg)?,
+    };
+    Ok(())
+}
+
+fn prepare_message(
+    repo: &Repository,
+    opts: &CommitOptions,
+    amend_base: Option<&Commit>,
+) -> Result<String, Error> {
+    let comment = message::comment_char(repo);
+
+    let given = match &opts.file {
+        Some(file) => Some(read_message_file(file)?),
+        None if !opts.messages.is_empty() => Some(opts.messages.join("\n\n")),
+        None => None,
+    };
+    // prepare-commit-msg 훅의 두 번째, 세 번째 인자
+    let mut source: Vec<String> = Vec::new();
+    if given.is_some() {
+        source.push("message".to_string());
+    }
+
+    // --amend 에 새 메시지가 없으면 기존 메시지로 시작한다
+    let (given, use_editor) = match (given, amend_base) {
+        (None, Some(base)) => {
+            source.push("commit".to_string());
+            source.push(base.id().to_string());
+            let msg = String::from_utf8_lossy(base.message_bytes()).to_string();
+            (Some(msg), opts.edit || !opts.no_edit)
+        }
+        (given, _) => {
+            let use_editor = given.is_none() || opts.edit;
+            (given, use_editor)
+        }
+    };
+    let template = match given {
+        Some(_) => None,
+        None => message::read_template(repo)?,
+    };
+    if template.is_some() {
+        source.push("template".to_string());
+    }
+
+    let cleanup = match opts.cleanup {
+        Some(cleanup) => cleanup,
+        None => match repo.config()?.get_string("commit.cleanup") {
+            Ok(mode) if mode != "default" => Cleanup::parse(&mode)?,
+            _ if use_editor => Cleanup::Strip,
+            _ => Cleanup::Whitespace,
+        },
+    };
+    // git 처럼 scissors 는 에디터로 작성할 때만 자르고, 아니면 whitespace 와 같다
+    let cleanup = match cleanup {
+        Cleanup::Scissors if !use_editor => Cleanup::Whitespace,
+        cleanup => cleanup,
+    };
+
+    let mut content = given.or_else(|| template.clone()).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if use_editor {
+        content.push('\n');
+        content.push_str(&message::status_summary(repo, comment)?);
+    }
+
+    // 훅과 에디터 모두 COMMIT_EDITMSG 파일을 통해 메시지를 주고받는다
+    let path = message::commit_editmsg_path(repo);
+    let path_str = path.to_string_lossy().to_string();
+    fs::write(&path, content)
+        .map_err(|e| Error::from_str(&format!("COMMIT_EDITMSG 작성 실패: {}", e)))?;
+
+    let mut hook_args = vec![path_str.as_str()];
+    hook_args.extend(source.iter().map(String::as_str));
+    hooks::run_hook(repo, "prepare-commit-msg", &hook_args, None)?;
+
+    if use_editor {
+        message::launch_editor(repo, &path)?;
+    }
+    if !opts.no_verify {
+        hooks::run_hook(repo, "commit-msg", &[&path_str], None)?;
+    }
+
+    let edited = fs::read_to_string(&path)
+        .map_err(|e| Error::from_str(&format!("COMMIT_EDITMSG 읽기 실패: {}", e)))?;
+    let msg = message::cleanup_message(&edited, cleanup, comment);
+
+    // 템플릿을 그대로 두고 저장했다면 커밋하지 않는다 (git과 동일)
+    if use_editor
+        && let Some(template) = template
+        && message::cleanup_message(&template, cleanup, comment) == msg
+    {
+        return Err(Error::from_str(
+            "템플릿 메시지가 수정되지 않아 커밋을 중단합니다",
+        ));
+    }
+
+    if msg.trim().is_empty() {
+        return Err(Error::from_str("커밋 메시지가 비어 있어 커밋을 중단합니다"));
+    }
+    Ok(msg)
+}
+
+fn read_message_file(file: &str) -> Result<String, Error> {
+    if file == "-" {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .map_err(|e| Error::from_str(&format!("stdin 읽기 실패: {}", e)))?;
+        return Ok(buf);
+    }
+    fs::read_to_string(file)
+        .map_err(|e| Error::from_str(&format!("메시지 파일 읽기 실패 '{}': {}", file, e)))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::commands;
     use crate::test_helpers::get_repo;
     use serial_test::serial;
@@ -54,8 +291,160 @@ mod tests {
 
         assert_eq!(
             head_commit.message().unwrap(),
-            commit_msg,
+            format!("{}\n", commit_msg),
             "커밋 메시지가 다름"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_git_commit_paragraphs_and_file() {
+        let repo = get_repo();
+
+        fs::write("paragraph.txt", "paragraph").expect("failed to write file");
+        commands::git_add("paragraph.txt").expect("failed to add file");
+        let oid = git_commit_with(&CommitOptions {
+            messages: vec!["subject  ".to_string(), "body\n\n\n".to_string()],
+            ..Default::default()
+        })
+        .expect("failed to commit");
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(commit.message().unwrap(), "subject\n\nbody\n");
+
+        fs::write("paragraph.txt", "from file").expect("failed to write file");
+        commands::git_add("paragraph.txt").expect("failed to add file");
+        fs::write(".git/msg_file", "\nfile subject\n# kept without editor\n")
+            .expect("failed to write message file");
+        let oid = git_commit_with(&CommitOptions {
+            file: Some(".git/msg_file".to_string()),
+            ..Default::default()
+        })
+        .expect("failed to commit");
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(
+            commit.message().unwrap(),
+            "file subject\n# kept without editor\n"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_commit_author_override() {
+        let repo = get_repo();
+
+        fs::write("author.txt", "author").expect("failed to write file");
+        commands::git_add("author.txt").expect("failed to add file");
+        let oid = git_commit_with(&CommitOptions {
+            messages: vec!["author override".to_string()],
+            identity: IdentityOverride {
+                author: Some("Someone Else <else@example.com>".to_string()),
+                date: Some("2024-03-01T12:30:00+09:00".to_string()),
+            },
+            ..Default::default()
+        })
+        .expect("failed to commit");
+
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(commit.author().name(), Some("Someone Else"));
+        assert_eq!(commit.author().when().seconds(), 1709263800);
+        assert_ne!(commit.committer().name(), Some("Someone Else"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_commit_amend_with_trailer() {
+        let repo = get_repo();
+
+        fs::write("amend.txt", "before").expect("failed to write file");
+        commands::git_add("amend.txt").expect("failed to add file");
+        let original = commands::git_commit("amend me").expect("failed to commit");
+        let original = repo.find_commit(original.parse().unwrap()).unwrap();
+
+        fs::write("amend.txt", "after").expect("failed to write file");
+        commands::git_add("amend.txt").expect("failed to add file");
+        let opts = CommitOptions {
+            amend: true,
+            no_edit: true,
+            trailers: vec![("Refs".to_string(), "#3".to_string())],
+            ..Default::default()
+        };
+        let amended = git_commit_with(&opts).expect("failed to amend");
+        let amended = repo.find_commit(amended.parse().unwrap()).unwrap();
+
+        assert_eq!(amended.message().unwrap(), "amend me\n\nRefs: #3\n");
+        assert_eq!(
+            amended.parent_ids().collect::<Vec<_>>(),
+            original.parent_ids().collect::<Vec<_>>()
+        );
+        assert_eq!(amended.author().when(), original.author().when());
+
+        // 같은 trailer 로 다시 amend 해도 중복되지 않는다
+        let amended = git_commit_with(&opts).expect("failed to amend");
+        let amended = repo.find_commit(amended.parse().unwrap()).unwrap();
+        assert_eq!(amended.message().unwrap(), "amend me\n\nRefs: #3\n");
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_commit_editor_and_template() {
+        let repo = get_repo();
+        let mut cfg = repo.config().unwrap();
+        let prev_editor = std::env::var("GIT_EDITOR").ok();
+
+        fs::write("editor.txt", "editor").expect("failed to write file");
+        commands::git_add("editor.txt").expect("failed to add file");
+
+        // 에디터 대신 메시지를 COMMIT_EDITMSG 앞에 덧붙이는 스크립트 사용
+        // SAFETY: serial 테스트라 다른 스레드가 환경 변수를 읽지 않는다
+        unsafe {
+            std::env::set_var(
+                "GIT_EDITOR",
+                "f() { printf 'from editor\\n\\n' | cat - \"$1\" > \"$1.tmp\" && mv \"$1.tmp\" \"$1\"; }; f",
+            );
+        }
+        let oid = git_commit_with(&CommitOptions::default()).expect("failed to commit");
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(commit.message().unwrap(), "from editor\n");
+        let editmsg = fs::read_to_string(".git/COMMIT_EDITMSG").unwrap();
+        assert!(editmsg.contains("# Changes to be committed:"));
+        assert!(editmsg.contains("new file:   editor.txt"));
+
+        // 템플릿을 수정하지 않고 저장하면 커밋이 중단되어야 함
+        fs::write(".git/commit_template", "template subject\n").unwrap();
+        cfg.set_str("commit.template", ".git/commit_template")
+            .unwrap();
+        unsafe {
+            std::env::set_var("GIT_EDITOR", "true");
+        }
+        fs::write("editor.txt", "template").expect("failed to write file");
+        commands::git_add("editor.txt").expect("failed to add file");
+        assert!(git_commit_with(&CommitOptions::default()).is_err());
+
+        cfg.remove("commit.template").unwrap();
+
+        // commit.cleanup=scissors 는 가위 줄부터 잘라낸다. -m 메시지는 whitespace 와 같다
+        cfg.set_str("commit.cleanup", "scissors").unwrap();
+        unsafe {
+            std::env::set_var(
+                "GIT_EDITOR",
+                "f() { printf 'cut subject\\n# ------------------------ >8 ------------------------\\ncut me\\n' | cat - \"$1\" > \"$1.tmp\" && mv \"$1.tmp\" \"$1\"; }; f",
+            );
+        }
+        let oid = git_commit_with(&CommitOptions::default()).expect("failed to commit");
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(commit.message().unwrap(), "cut subject\n");
+        fs::write("editor.txt", "scissors").expect("failed to write file");
+        commands::git_add("editor.txt").expect("failed to add file");
+        let oid = git_commit("scissors\n# kept").expect("failed to commit");
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(commit.message().unwrap(), "scissors\n# kept\n");
+        cfg.remove("commit.cleanup").unwrap();
+
+        unsafe {
+            match prev_editor {
+                Some(editor) => std::env::set_var("GIT_EDITOR", editor),
+                None => std::env::remove_var("GIT_EDITOR"),
+            }
+        }
+    }
 }
@@ -0,0 +1,364 @@
+use crate::commands::log::{self, LogOptions};
+use crate::pretty::{Format, Pretty};
+use git2::{Commit, Error, ObjectType, Oid, Repository, Tree};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    // Graphviz DOT
+    #[default]
+    Dot,
+    // Mermaid gitGraph
+    Mermaid,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    // --objects: 커밋의 트리와 blob 까지 (dot 만)
+    pub objects: bool,
+    // --label <format>: 노드 이름, log --format 자리표시자 (기본 "%h %s")
+    pub label: Option<String>,
+    // 범위와 필터는 log 와 같다
+    pub log: LogOptions,
+}
+
+// 커밋 DAG 를 DOT 또는 Mermaid 텍스트로
+pub fn git_export(opts: &ExportOptions) -> Result<String, Error> {
+    let repo = Repository::open(".")?;
+    let log_opts = LogOptions {
+        topo_order: true,
+        ..opts.log.clone()
+    };
+    // 부모가 자식보다 먼저 나오도록 뒤집는다
+    let mut commits = log::walk_commits(&repo, &log_opts)?.collect::<Result<Vec<_>, _>>()?;
+    commits.reverse();
+
+    let decorations = log::decorations(&repo, false)?;
+    let format = Format::Custom(opts.label.clone().unwrap_or_else(|| "%h %s".to_string()));
+    let pretty = Pretty {
+        format: &format,
+        date: &opts.log.date,
+        color: false,
+    };
+    let label = |commit: &Commit| {
+        let names = decorations.get(&commit.id()).map_or(&[][..], Vec::as_slice);
+        pretty.lines(commit, names).join("\n")
+    };
+
+    match opts.format {
+        ExportFormat::Dot => export_dot(&repo, &commits, label, opts.objects),
+        ExportFormat::Mermaid if opts.objects => {
+            Err(Error::from_str("mermaid 형식은 --objects 를 지원하지 않음"))
+        }
+        ExportFormat::Mermaid => export_mermaid(&repo, &commits, label),
+    }
+}
+
+fn export_dot(
+    repo: &Repository,
+    commits: &[Commit],
+    label: impl Fn(&Commit) -> String,
+    objects: bool,
+) -> Result<String, Error> {
+    let included: HashSet<Oid> = commits.iter().map(Commit::id).collect();
+    let mut lines = vec![
+        "digraph git {".to_string(),
+        "    rankdir=RL;".to_string(),
+        "    node [shape=box, style=rounded];".to_string(),
+    ];
+
+    let mut seen = HashSet::new();
+    for commit in commits {
+        lines.push(format!(
+            "    \"{}\" [label=\"{}\"];",
+            commit.id(),
+            escape(&label(commit))
+        ));
+        // 범위 밖의 부모는 그리지 않는다
+        for parent in commit.parent_ids().filter(|id| included.contains(id)) {
+            lines.push(format!("    \"{}\" -> \"{}\";", commit.id(), parent));
+        }
+        if objects {
+            let tree = commit.tree()?;
+            lines.push(format!("    \"{}\" -> \"{}\";", commit.id(), tree.id()));
+            dot_tree(repo, &tree, &mut seen, &mut lines)?;
+        }
+    }
+
+    // 브랜치, 태그, HEAD
+    let head = repo.head().ok();
+    for reference in repo.references()? {
+        let reference = reference?;
+        let (Some(name), Ok(commit)) = (reference.name(), reference.peel_to_commit()) else {
+            continue;
+        };
+        if !included.contains(&commit.id()) {
+            continue;
+        }
+        let color = if reference.is_tag() {
+            "lightyellow"
+        } else if reference.is_remote() {
+            "lightpink"
+        } else if reference.is_branch() {
+            "palegreen"
+        } else {
+            continue;
+        };
+        lines.push(format!(
+            "    \"{}\" [label=\"{}\", shape=box, style=filled, fillcolor={}];",
+            name,
+            escape(reference.shorthand().unwrap_or(name)),
+            color
+        ));
+        lines.push(format!("    \"{}\" -> \"{}\";", name, commit.id()));
+    }
+    if let Some(head) = &head {
+        let target = match head.name() {
+            Some(name) if head.is_branch() => Some(name.to_string()),
+            _ => head.target().map(|oid| oid.to_string()),
+        };
+        if let Some(target) = target {
+            lines.push("    \"HEAD\" [shape=box, style=filled, fillcolor=lightblue];".to_string());
+            lines.push(format!("    \"HEAD\" -> \"{}\";", target));
+        }
+    }
+
+    lines.push("}".to_string());
+    Ok(lines.join("\n"))
+}
+
+// 트리와 그 아래 트리/blob 을 한 번씩만
+fn dot_tree(
+    repo: &Repository,
+    tree: &Tree,
+    seen: &mut HashSet<Oid>,
+    lines: &mut Vec<String>,
+) -> Result<(), Error> {
+    if !seen.insert(tree.id()) {
+        return Ok(());
+    }
+    lines.push(format!(
+        "    \"{}\" [label=\"tree {}\", shape=folder];",
+        tree.id(),
+        &tree.id().to_string()[..7]
+    ));
+    for entry in tree.iter() {
+        let name = entry.name().unwrap_or("");
+        lines.push(format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"];",
+            tree.id(),
+            entry.id(),
+            escape(name)
+        ));
+        match entry.kind() {
+            Some(ObjectType::Tree) => {
+                let subtree = repo.find_tree(entry.id())?;
+                dot_tree(repo, &subtree, seen, lines)?;
+            }
+            _ => {
+                if seen.insert(entry.id()) {
+                    lines.push(format!(
+                        "    \"{}\" [label=\"blob {}\", shape=note];",
+                        entry.id(),
+                        &entry.id().to_string()[..7]
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Mermaid gitGraph 는 브랜치 단위로 그리므로 커밋마다 브랜치를 하나 정해준다.
+// HEAD 브랜치, 다른 로컬 브랜치 순서로 first-parent 를 따라가며 정하고,
+// 남은 커밋은 "branch-<hash>" 이름을 붙인다
+fn export_mermaid(
+    repo: &Repository,
+    commits: &[Commit],
+    label: impl Fn(&Commit) -> String,
+) -> Result<String, Error> {
+    let included: HashSet<Oid> = commits.iter().map(Commit::id).collect();
+    let by_id: HashMap<Oid, &Commit> = commits.iter().map(|commit| (commit.id(), commit)).collect();
+
+    let mut tips: Vec<(String, Oid)> = Vec::new();
+    let head_branch = repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| Some((head.shorthand()?.to_string(), head.target()?)));
+    tips.extend(head_branch.clone());
+    for branch in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let (Some(name), Some(target)) = (branch.name()?, branch.get().target())
+            && head_branch.as_ref().is_none_or(|(head, _)| head != name)
+        {
+            tips.push((name.to_string(), target));
+        }
+    }
+    for commit in commits.iter().rev() {
+        tips.push((
+            format!("branch-{}", &commit.id().to_string()[..7]),
+            commit.id(),
+        ));
+    }
+
+    let mut branch_of: HashMap<Oid, String> = HashMap::new();
+    for (name, tip) in tips {
+        let mut next = Some(tip);
+        while let Some(oid) =
+            next.filter(|oid| included.contains(oid) && !branch_of.contains_key(oid))
+        {
+            branch_of.insert(oid, name.clone());
+            next = by_id[&oid].parent_ids().next();
+        }
+    }
+
+    let tags: HashMap<Oid, String> = {
+        let mut tags: HashMap<Oid, Vec<String>> = HashMap::new();
+        for name in repo.tag_names(None)?.iter().flatten() {
+            if let Ok(commit) = repo
+                .revparse_single(&format!("refs/tags/{}", name))
+                .and_then(|object| object.peel_to_commit())
+            {
+                tags.entry(commit.id()).or_default().push(name.to_string());
+            }
+        }
+        tags.into_iter()
+            .map(|(oid, names)| (oid, names.join(", ")))
+            .collect()
+    };
+
+    // 브랜치마다 처음 나오는 커밋의 부모 (그 커밋 바로 뒤에 브랜치를 만들어 둔다)
+    let mut forks: HashMap<Oid, Vec<&str>> = HashMap::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for commit in commits {
+        let branch = branch_of[&commit.id()].as_str();
+        if seen.insert(branch)
+            && let Some(parent) = commit
+                .parent_ids()
+                .next()
+                .filter(|id| included.contains(id))
+        {
+            forks.entry(parent).or_default().push(branch);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut created: HashSet<&str> = HashSet::new();
+    let mut current: Option<&str> = None;
+    for commit in commits {
+        let branch = branch_of[&commit.id()].as_str();
+        if current.is_none() {
+            // 첫 커밋의 브랜치를 메인 브랜치로
+            lines.push(format!(
+                "%%{{init: {{ 'gitGraph': {{ 'mainBranchName': '{}' }} }} }}%%",
+                branch
+            ));
+            lines.push("gitGraph".to_string());
+            created.insert(branch);
+        } else if created.insert(branch) {
+            // 범위 안에 갈라진 지점이 없는 브랜치는 지금 위치에서 만든다
+            lines.push(format!("    branch {}", branch));
+        } else if current != Some(branch) {
+            lines.push(format!("    checkout {}", branch));
+        }
+        current = Some(branch);
+
+        let mut attrs = format!("id: \"{}\"", mermaid_escape(&label(commit)));
+        if let Some(tag) = tags.get(&commit.id()) {
+            attrs.push_str(&format!(" tag: \"{}\"", mermaid_escape(tag)));
+        }
+        let merged = commit
+            .parent_ids()
+            .skip(1)
+            .filter(|id| included.contains(id))
+            .map(|id| branch_of[&id].as_str())
+            .find(|&merged| merged != branch);
+        match merged {
+            Some(merged) => lines.push(format!("    merge {} {}", merged, attrs)),
+            None => lines.push(format!("    commit {}", attrs)),
+        }
+
+        for &fork in forks.get(&commit.id()).into_iter().flatten() {
+            if created.insert(fork) {
+                lines.push(format!("    branch {}", fork));
+                current = Some(fork);
+            }
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// gitGraph 의 id 는 따옴표와 줄바꿈을 쓸 수 없다
+fn mermaid_escape(label: &str) -> String {
+    label.replace('"', "'").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{get_repo, merge_history};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_git_export() {
+        let (_dir, [_, side, _, merge]) = merge_history();
+
+        let dot = git_export(&ExportOptions::default()).unwrap();
+        assert!(dot.starts_with("digraph git {"));
+        assert!(dot.contains(&format!(
+            "\"{}\" [label=\"{} side\"];",
+            side,
+            &side.to_string()[..7]
+        )));
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\";", merge, side)));
+        assert!(dot.contains(&format!("\"refs/tags/v1\" -> \"{}\";", side)));
+        assert!(dot.contains("\"HEAD\" -> \"refs/heads/main\";"));
+        assert!(!dot.contains("shape=folder"));
+
+        let opts = ExportOptions {
+            objects: true,
+            ..Default::default()
+        };
+        assert!(git_export(&opts).unwrap().contains("shape=folder"));
+
+        let opts = ExportOptions {
+            format: ExportFormat::Mermaid,
+            label: Some("%s".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            git_export(&opts).unwrap(),
+            [
+                "%%{init: { 'gitGraph': { 'mainBranchName': 'main' } } }%%",
+                "gitGraph",
+                "    commit id: \"root\"",
+                "    branch side",
+                "    checkout main",
+                "    commit id: \"main\"",
+                "    checkout side",
+                "    commit id: \"side\" tag: \"v1\"",
+                "    checkout main",
+                "    merge side id: \"merge\"",
+            ]
+            .join("\n")
+        );
+
+        let opts = ExportOptions {
+            format: ExportFormat::Mermaid,
+            objects: true,
+            ..Default::default()
+        };
+        assert!(git_export(&opts).is_err());
+        get_repo();
+    }
+}
@@ -3,11 +3,77 @@ pub fn git_help() {
     println!("init: .git 생성");
     println!("add <path>: 변경 사항을 스테이지에 올림");
     println!("commit <msg>: 변경 사항을 기록");
+    println!("commit [-m <msg>]... [-F <file>] [-e]: 메시지 없이 실행하면 에디터로 작성");
+    println!("commit --author \"Name <email>\" --date <date>: 작성자, 작성 시각 지정");
+    println!("commit --amend [--no-edit]: 마지막 커밋을 수정");
+    println!("commit/revert --trailer <key:value> -s: trailer, Signed-off-by 추가");
+    println!("commit/revert -S: 커밋 서명 (gpg.format=ssh 면 ssh-keygen, 아니면 gpg)");
     println!("push <remote> <refspec>: 기록된 사항을 remote에 전송");
+    println!("commit/push --no-verify: pre-commit, commit-msg, pre-push 훅을 건너뜀");
     println!("revert <commit_id>: commit된 기록을 롤백");
-    println!("reset <file_path> <soft|mixed|hard>: file의 변경 상태를 이전으로 변경");
+    println!("revert <commit>... | <A..B>: 여러 커밋을 최신 커밋부터 차례로 롤백");
+    println!("revert -m <parent> <merge>: 머지 커밋을 <parent> 번째 부모 기준으로 롤백");
+    println!("revert -n <commit>: 커밋하지 않고 인덱스와 작업 트리에만 롤백 (커밋 하나만)");
+    println!("revert --continue | --skip | --abort: 충돌 해결 후 계속 / 이번 커밋 건너뜀 / 취소");
+    println!(
+        "restore [--staged] [--worktree] [--source <rev>] <path>...: 파일 복원 (기본: 인덱스에서 작업 트리로, --staged: unstage)"
+    );
+    println!(
+        "reset [--soft|--mixed|--hard|--keep|--merge] [<rev>]: 현재 브랜치를 <rev> 로 옮김 (기본 --mixed)"
+    );
+    println!("reset [<rev>] [--] <path>...: 인덱스의 해당 경로만 <rev> 로 되돌림");
+    println!(
+        "merge [--ff|--ff-only|--no-ff] <branch>: 브랜치를 현재 브랜치에 머지 (기본: 가능하면 fast-forward)"
+    );
+    println!("merge --continue | --abort: 충돌 해결 후 머지 커밋 생성 / 머지 전으로 되돌림");
     println!("log: 로그 출력");
-    println!("branch: 브랜치 출력");
-    println!("checkout <name>: <name> 브랜치로 체크아웃");
+    println!("log [-n <N>] [--skip <N>]: 출력 개수 제한, 앞의 N개 건너뜀");
+    println!("log --author/--committer/--grep <re> [-i]: 작성자, 커미터, 메시지를 정규식으로 필터");
+    println!("log --since/--until <date>: 커밋 날짜 범위 (예: 2024-03-01, \"2 weeks ago\")");
+    println!(
+        "log [<rev>|<A..B>|<A...B>|^<rev>]...: 시작점, 범위 지정 (A...B 는 </> 로 어느 쪽인지 표시)"
+    );
+    println!("log --all | --branches: 모든 ref / 모든 로컬 브랜치부터 출력");
+    println!("log [--] <path>...: 해당 경로를 변경한 커밋만 출력");
+    println!("log --oneline | --pretty=<oneline|short|medium|full|fuller|format:...>: 출력 형식");
+    println!(
+        "log --date=<default|relative|iso|iso-strict|rfc|short|raw|unix|format:...>: 날짜 형식"
+    );
+    println!("log --graph [--no-color]: 브랜치/태그 표시와 함께 커밋 그래프 출력");
+    println!("log --trailer <key>[=<value>]: 해당 trailer 가 있는 커밋만 출력");
+    println!("log --show-signature: 커밋 서명 확인 결과 출력");
+    println!(
+        "shortlog [-s] [-e] [-n] [<log 옵션>]: 작성자별 커밋 제목 (-s: 개수만, -e: 이메일, -n: 많은 순)"
+    );
+    println!("stats [<log 옵션>]: 작성자별 커밋/추가/삭제 줄 수, 주별 커밋 수");
+    println!(
+        "export [--dot|--mermaid] [--objects] [--label <format>] [-o <file>] [<log 옵션>]: 커밋 DAG 를 DOT/Mermaid 로 내보냄"
+    );
+    println!(
+        "reflog [show] [<ref>]: HEAD/브랜치가 가리켰던 커밋 기록 (<ref>@{{n}} 으로 리비전 지정 가능)"
+    );
+    println!("recover [<commit> <branch>]: 잃어버린 커밋 목록 / 해당 커밋에 브랜치를 다시 만듦");
+    println!("show [<rev>]: 커밋 정보, 서명 상태, 변경 내용 출력");
+    println!("tag [-a] [-s] [-m <msg>] <name> [<commit>]: 태그 생성 (-s: 서명)");
+    println!("tag [-d|-v] <name>: 태그 삭제 / 서명 확인");
+    println!("lint [<commit>|<A..B>]: 커밋 메시지를 Conventional Commits 규칙으로 검사");
+    println!(
+        "branch [-v|-vv] [-r|-a]: 브랜치 출력 (-v: 마지막 커밋, ahead/behind, -vv: upstream 이름, -r: 원격, -a: 전부)"
+    );
+    println!(
+        "branch --set-upstream-to=<upstream> | --unset-upstream [<name>]: upstream 지정 / 해제"
+    );
+    println!("branch <name> [<start-point>]: 브랜치 생성 (원격 브랜치에서 만들면 upstream 지정)");
+    println!("branch -d|-D <name>...: 브랜치 삭제 (-d 는 머지된 브랜치만, -D 는 강제)");
+    println!("branch -m|-M|-c|-C [<old>] <new>: 브랜치 이름 변경 / 복사 (대문자는 덮어쓰기)");
+    println!(
+        "checkout [-f] [-m] <name>: <name> 브랜치로 체크아웃 (로컬 변경을 덮어쓰면 거부, -f: 버림, -m: 머지해서 옮김)"
+    );
+    println!("checkout -b <new> [<start-point>]: 새 브랜치를 만들고 체크아웃");
+    println!("checkout -: 직전 브랜치로, 원격에만 있는 브랜치는 추적 브랜치를 만들어 체크아웃");
+    println!(
+        "switch [-f] [-m] <branch> | switch -c <new> [<start-point>] | switch --detach <commit>: 브랜치 이동"
+    );
+    println!("checkout --orphan <name>: 커밋이 없는 새 브랜치로 이동 (인덱스 유지)");
     println!("q: 종료")
 }
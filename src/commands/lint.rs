@@ -0,0 +1,427 @@
+use crate::message;
+use git2::{Error, Oid, Repository};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintMode {
+    Off,
+    // 위반 사항을 출력만 하고 커밋은 진행
+    Warn,
+    // 위반 사항이 있으면 커밋 중단
+    Block,
+}
+
+// git config 의 lint.* 값으로 설정한다
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    pub mode: LintMode,
+    pub types: Vec<String>,
+    // 비어 있으면 scope 이름을 검사하지 않음
+    pub scopes: Vec<String>,
+    pub require_scope: bool,
+    pub header_max_length: usize,
+    pub body_max_line_length: usize,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            mode: LintMode::Off,
+            types: [
+                "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore",
+                "revert",
+            ]
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+            scopes: Vec::new(),
+            require_scope: false,
+            header_max_length: 72,
+            body_max_line_length: 100,
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn from_repo(repo: &Repository) -> Result<LintConfig, Error> {
+        let cfg = repo.config()?;
+        let mut lint = LintConfig::default();
+
+        if let Ok(mode) = cfg.get_string("lint.mode") {
+            lint.mode = match mode.as_str() {
+                "off" => LintMode::Off,
+                "warn" => LintMode::Warn,
+                "block" => LintMode::Block,
+                _ => {
+                    return Err(Error::from_str(&format!(
+                        "lint.mode 는 off, warn, block 중 하나여야 함: {}",
+                        mode
+                    )));
+                }
+            };
+        }
+        if let Ok(types) = cfg.get_string("lint.types") {
+            lint.types = split_list(&types);
+        }
+        if let Ok(scopes) = cfg.get_string("lint.scopes") {
+            lint.scopes = split_list(&scopes);
+        }
+        if let Ok(require) = cfg.get_bool("lint.requireScope") {
+            lint.require_scope = require;
+        }
+        if let Ok(max) = cfg.get_i64("lint.headerMaxLength") {
+            lint.header_max_length = max.max(1) as usize;
+        }
+        if let Ok(max) = cfg.get_i64("lint.bodyMaxLineLength") {
+            lint.body_max_line_length = max.max(1) as usize;
+        }
+        Ok(lint)
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    // 1부터 시작
+    pub line: usize,
+    pub column: usize,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} [{}] {}",
+            self.line, self.column, self.rule, self.message
+        )
+    }
+}
+
+pub fn lint_message(msg: &str, cfg: &LintConfig) -> Vec<Violation> {
+    let mut res = Vec::new();
+    let lines: Vec<&str> = msg.lines().collect();
+    let Some(header) = lines.first() else {
+        push(&mut res, 1, 1, "header-empty", "커밋 메시지가 비어 있음");
+        return res;
+    };
+    lint_header(header, cfg, &mut res);
+
+    if lines.len() > 1 && !lines[1].trim().is_empty() {
+        push(
+            &mut res,
+            2,
+            1,
+            "body-leading-blank",
+            "제목과 본문 사이에는 빈 줄이 필요함",
+        );
+    }
+
+    // 마지막 문단에 trailer 가 하나라도 있으면 footer 로 취급
+    let footer_start = lines
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map(|pos| pos + 1)
+        .filter(|&start| {
+            lines[start..]
+                .iter()
+                .any(|line| message::parse_trailer_line(line).is_some())
+        });
+
+    for (idx, line) in lines.iter().enumerate().skip(1) {
+        if footer_start.is_some_and(|start| idx >= start) {
+            // 공백으로 시작하는 줄은 이전 trailer 값의 연속
+            if message::parse_trailer_line(line).is_none() && !line.starts_with([' ', '\t']) {
+                push(
+                    &mut res,
+                    idx + 1,
+                    1,
+                    "trailer-format",
+                    "footer 는 'Token: value' 형식이어야 함",
+                );
+            }
+            continue;
+        }
+        if line.chars().count() > cfg.body_max_line_length {
+            push(
+                &mut res,
+                idx + 1,
+                cfg.body_max_line_length + 1,
+                "body-max-line-length",
+                &format!(
+                    "본문 줄 길이는 {}자를 넘을 수 없음",
+                    cfg.body_max_line_length
+                ),
+            );
+        }
+    }
+    res
+}
+
+fn lint_header(header: &str, cfg: &LintConfig, res: &mut Vec<Violation>) {
+    if header.chars().count() > cfg.header_max_length {
+        push(
+            res,
+            1,
+            cfg.header_max_length + 1,
+            "header-max-length",
+            &format!("제목은 {}자를 넘을 수 없음", cfg.header_max_length),
+        );
+    }
+
+    let Some(colon) = header.find(':') else {
+        push(
+            res,
+            1,
+            1,
+            "header-format",
+            "제목은 'type(scope): subject' 형식이어야 함",
+        );
+        return;
+    };
+
+    let prefix = &header[..colon];
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let (ty, scope) = match prefix.find('(') {
+        Some(open) => {
+            let Some(inner) = prefix[open + 1..].strip_suffix(')') else {
+                push(
+                    res,
+                    1,
+                    column(header, open),
+                    "scope-format",
+                    "scope 는 '(' 와 ')' 로 감싸야 함",
+                );
+                return;
+            };
+            (&prefix[..open], Some((open + 1, inner)))
+        }
+        None => (prefix, None),
+    };
+
+    if ty.is_empty() || !ty.chars().all(|c| c.is_ascii_lowercase()) {
+        push(res, 1, 1, "type-case", "type 은 영문 소문자여야 함");
+    } else if !cfg.types.iter().any(|t| t == ty) {
+        push(
+            res,
+            1,
+            1,
+            "type-enum",
+            &format!(
+                "허용되지 않은 type '{}' (허용: {})",
+                ty,
+                cfg.types.join(", ")
+            ),
+        );
+    }
+
+    match scope {
+        None if cfg.require_scope => {
+            push(
+                res,
+                1,
+                column(header, ty.len()),
+                "scope-empty",
+                "scope 가 필요함",
+            );
+        }
+        Some((start, "")) => {
+            push(
+                res,
+                1,
+                column(header, start),
+                "scope-empty",
+                "scope 가 비어 있음",
+            );
+        }
+        Some((start, scope))
+            if !cfg.scopes.is_empty() && !cfg.scopes.iter().any(|s| s == scope) =>
+        {
+            push(
+                res,
+                1,
+                column(header, start),
+                "scope-enum",
+                &format!(
+                    "허용되지 않은 scope '{}' (허용: {})",
+                    scope,
+                    cfg.scopes.join(", ")
+                ),
+            );
+        }
+        _ => {}
+    }
+
+    let rest = &header[colon + 1..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        push(
+            res,
+            1,
+            column(header, colon + 1),
+            "subject-space",
+            "':' 뒤에는 공백이 필요함",
+        );
+    }
+    let subject = rest.trim();
+    if subject.is_empty() {
+        push(
+            res,
+            1,
+            column(header, colon + 1),
+            "subject-empty",
+            "subject 가 비어 있음",
+        );
+    } else if subject.ends_with('.') {
+        push(
+            res,
+            1,
+            header.trim_end().chars().count(),
+            "subject-full-stop",
+            "subject 는 '.' 으로 끝나면 안 됨",
+        );
+    }
+}
+
+// 바이트 위치를 1부터 시작하는 문자 단위 컬럼으로 변환
+fn column(line: &str, byte_idx: usize) -> usize {
+    line[..byte_idx].chars().count() + 1
+}
+
+fn push(res: &mut Vec<Violation>, line: usize, column: usize, rule: &'static str, msg: &str) {
+    res.push(Violation {
+        line,
+        column,
+        rule,
+        message: msg.to_string(),
+    });
+}
+
+// git_commit 에서 lint.mode 에 따라 경고 또는 중단
+pub fn check_commit_message(repo: &Repository, msg: &str) -> Result<(), Error> {
+    let cfg = LintConfig::from_repo(repo)?;
+    if cfg.mode == LintMode::Off {
+        return Ok(());
+    }
+    let violations = lint_message(msg, &cfg);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let report = violations
+        .iter()
+        .map(|v| format!("  {}", v))
+        .collect::<Vec<_>>()
+        .join("\n");
+    match cfg.mode {
+        LintMode::Block => Err(Error::from_str(&format!(
+            "커밋 메시지 lint 실패:\n{}",
+            report
+        ))),
+        _ => {
+            println!("커밋 메시지 lint 경고:\n{}", report);
+            Ok(())
+        }
+    }
+}
+
+// 범위("A..B") 또는 단일 커밋(기본 HEAD)의 메시지를 검사한다
+// 위반 사항이 있는 커밋만 (커밋 요약, 위반 목록) 으로 반환
+pub fn git_lint(range: Option<&str>) -> Result<Vec<(String, Vec<Violation>)>, Error> {
+    let repo = Repository::open(".")?;
+    let cfg = LintConfig::from_repo(&repo)?;
+
+    let oids: Vec<Oid> = match range {
+        Some(range) if range.contains("..") => {
+            let mut revwalk = repo.revwalk()?;
+            revwalk.push_range(range)?;
+            revwalk.collect::<Result<_, _>>()?
+        }
+        Some(rev) => vec![repo.revparse_single(rev)?.peel_to_commit()?.id()],
+        None => vec![repo.head()?.peel_to_commit()?.id()],
+    };
+
+    let mut res = Vec::new();
+    for oid in oids {
+        let commit = repo.find_commit(oid)?;
+        let msg = String::from_utf8_lossy(commit.message_bytes()).to_string();
+        let violations = lint_message(&msg, &cfg);
+        if !violations.is_empty() {
+            let id = oid.to_string();
+            let summary = msg.lines().next().unwrap_or("");
+            res.push((format!("{} {}", &id[..7], summary), violations));
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands;
+    use crate::test_helpers::get_repo;
+    use serial_test::serial;
+    use std::fs;
+
+    fn rules(msg: &str, cfg: &LintConfig) -> Vec<&'static str> {
+        lint_message(msg, cfg).iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn test_lint_message_valid() {
+        let cfg = LintConfig::default();
+        let msg = "feat(log)!: add graph output\n\nbody line\n\nRefs #12\nBREAKING CHANGE: removed flag\n";
+        assert!(lint_message(msg, &cfg).is_empty());
+    }
+
+    #[test]
+    fn test_lint_message_violations() {
+        let mut cfg = LintConfig::default();
+        assert_eq!(rules("added stuff", &cfg), vec!["header-format"]);
+        assert_eq!(rules("Feat: x", &cfg), vec!["type-case"]);
+        assert_eq!(rules("feature: x", &cfg), vec!["type-enum"]);
+        assert_eq!(
+            rules("fix:x.", &cfg),
+            vec!["subject-space", "subject-full-stop"]
+        );
+        assert_eq!(
+            rules("fix: x\nbody without blank\n", &cfg),
+            vec!["body-leading-blank"]
+        );
+        assert_eq!(
+            rules("fix: x\n\nbody\n\nSigned-off-by: a\nnot trailer\n", &cfg),
+            vec!["trailer-format"]
+        );
+
+        cfg.scopes = vec!["log".to_string()];
+        cfg.body_max_line_length = 10;
+        let violations = lint_message("fix(commit): x\n\nthis line is too long\n", &cfg);
+        assert_eq!(violations[0].rule, "scope-enum");
+        assert_eq!((violations[0].line, violations[0].column), (1, 5));
+        assert_eq!(violations[1].rule, "body-max-line-length");
+        assert_eq!((violations[1].line, violations[1].column), (3, 11));
+    }
+
+    #[test]
+    #[serial]
+    fn test_commit_blocked_by_lint() {
+        let repo = get_repo();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("lint.mode", "block").unwrap();
+
+        fs::write("lint.txt", "lint").expect("failed to write file");
+        commands::git_add("lint.txt").expect("failed to add file");
+        assert!(commands::git_commit("not conventional").is_err());
+        assert!(commands::git_commit("chore: conventional").is_ok());
+
+        cfg.remove("lint.mode").unwrap();
+    }
+}
@@ -1,16 +1,425 @@
-use git2::Repository;
+use crate::date::{self, DateFormat};
+use crate::graph::Graph;
+use crate::message;
+use crate::pretty::{self, Format, Pretty, paint};
+use crate::signing;
+use git2::{Commit, DiffOptions, Error, Oid, Repository, RevparseMode, Revwalk, Sort, Tree};
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
-pub fn git_log() -> Result<Vec<String>, git2::Error> {
-    let repo = Repository::open(".")?;
+#[derive(Debug, Default, Clone)]
+pub struct LogOptions {
+    // --trailer <key>[=<value>]: 키가 일치하고 값에 <value> 가 포함된 trailer 를 가진 커밋만
+    pub trailer: Option<(String, Option<String>)>,
+    // --show-signature: 각 커밋 아래에 서명 확인 결과 출력
+    pub show_signature: bool,
+    // -n <N> / -<N> / --max-count: 최대 출력 개수
+    pub max_count: Option<usize>,
+    // --skip <N>: 조건에 맞는 커밋 중 앞의 N개를 건너뜀
+    pub skip: usize,
+    // --author / --committer: "Name <email>" 에 대한 정규식
+    pub author: Option<String>,
+    pub committer: Option<String>,
+    // --grep: 커밋 메시지에 대한 정규식, 여러 개면 하나만 맞아도 통과
+    pub grep: Vec<String>,
+    // -i / --regexp-ignore-case
+    pub ignore_case: bool,
+    // --since / --until: 커미터 날짜 기준 범위 (date::parse_date 형식)
+    pub since: Option<String>,
+    pub until: Option<String>,
+    // -- <path>...: 해당 경로를 변경한 커밋만
+    pub paths: Vec<String>,
+    // --graph: lane 과 브랜치/태그 표시를 포함한 그래프로 출력
+    pub graph: bool,
+    // --topo-order: 자식이 항상 부모보다 먼저 나오도록 (--graph 는 항상)
+    pub topo_order: bool,
+    // 그래프와 브랜치/태그 표시에 ANSI 색을 입힘
+    pub color: bool,
+    // <rev>, A..B, A...B, ^<rev>: 없으면 HEAD 부터
+    pub revs: Vec<String>,
+    // --pretty / --format / --oneline: 없으면 "<hash>: <subject>" (--graph 는 oneline)
+    pub format: Option<Format>,
+    // --date=<format>
+    pub date: DateFormat,
+    // --all: 모든 ref 와 HEAD 부터
+    pub all: bool,
+    // --branches: 모든 로컬 브랜치부터
+    pub branches: bool,
+}
+
+// log 조건을 미리 컴파일해 둔 것
+struct Filter {
+    trailer: Option<(String, Option<String>)>,
+    author: Option<Regex>,
+    committer: Option<Regex>,
+    grep: Vec<Regex>,
+    since: Option<i64>,
+    until: Option<i64>,
+    paths: Vec<String>,
+}
+
+impl Filter {
+    fn new(opts: &LogOptions, paths: Vec<String>) -> Result<Filter, Error> {
+        let regex = |pattern: &str| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(opts.ignore_case)
+                .build()
+                .map_err(|e| Error::from_str(&format!("잘못된 정규식 '{}': {}", pattern, e)))
+        };
+        let seconds = |input: &Option<String>| {
+            input
+                .as_deref()
+                .map(|input| date::parse_date(input).map(|time| time.seconds()))
+                .transpose()
+        };
+
+        Ok(Filter {
+            trailer: opts.trailer.clone(),
+            author: opts.author.as_deref().map(regex).transpose()?,
+            committer: opts.committer.as_deref().map(regex).transpose()?,
+            grep: opts
+                .grep
+                .iter()
+                .map(|pattern| regex(pattern))
+                .collect::<Result<_, _>>()?,
+            since: seconds(&opts.since)?,
+            until: seconds(&opts.until)?,
+            paths,
+        })
+    }
+
+    fn matches(&self, repo: &Repository, commit: &Commit) -> Result<bool, Error> {
+        let time = commit.committer().when().seconds();
+        if self.since.is_some_and(|since| time < since)
+            || self.until.is_some_and(|until| time > until)
+        {
+            return Ok(false);
+        }
+        if let Some(author) = &self.author
+            && !author.is_match(&commit.author().to_string())
+        {
+            return Ok(false);
+        }
+        if let Some(committer) = &self.committer
+            && !committer.is_match(&commit.committer().to_string())
+        {
+            return Ok(false);
+        }
+
+        let msg = String::from_utf8_lossy(commit.message_bytes());
+        if !self.grep.is_empty() && !self.grep.iter().any(|grep| grep.is_match(&msg)) {
+            return Ok(false);
+        }
+        if let Some((key, value)) = &self.trailer {
+            let found = message::parse_trailers(&msg).iter().any(|(k, v)| {
+                k.eq_ignore_ascii_case(key) && value.as_ref().is_none_or(|value| v.contains(value))
+            });
+            if !found {
+                return Ok(false);
+            }
+        }
+
+        if self.paths.is_empty() {
+            return Ok(true);
+        }
+        self.touches_paths(repo, commit)
+    }
+
+    // git 의 기본 history simplification 처럼, 부모 중 하나라도 해당 경로가 같으면 제외한다
+    fn touches_paths(&self, repo: &Repository, commit: &Commit) -> Result<bool, Error> {
+        let tree = commit.tree()?;
+        if commit.parent_count() == 0 {
+            return self.differs(repo, None, &tree);
+        }
+        for parent in commit.parents() {
+            if !self.differs(repo, Some(&parent.tree()?), &tree)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn differs(&self, repo: &Repository, old: Option<&Tree>, new: &Tree) -> Result<bool, Error> {
+        let mut diff_opts = DiffOptions::new();
+        for path in &self.paths {
+            diff_opts.pathspec(path);
+        }
+        let diff = repo.diff_tree_to_tree(old, Some(new), Some(&mut diff_opts))?;
+        Ok(diff.deltas().len() > 0)
+    }
+}
+
+// 시작점을 넣은 revwalk 와, 리비전이 아니라 경로로 해석된 인자들
+struct Revisions<'r> {
+    revwalk: Revwalk<'r>,
+    paths: Vec<String>,
+    // A...B 가 있으면 A 쪽에서만 닿는 커밋들 (나머지는 B 쪽)
+    left: Option<HashSet<Oid>>,
+}
+
+fn revisions<'r>(repo: &'r Repository, opts: &LogOptions) -> Result<Revisions<'r>, Error> {
     let mut revwalk = repo.revwalk()?;
-    revwalk.push_head()?;
 
-    let mut res = Vec::new();
-    for commit_id in revwalk {
-        let commit = repo.find_commit(commit_id?)?;
-        let hash = commit.id().to_string();
-        if let Some(msg) = commit.summary() {
-            res.push(format!("{}: {}", hash, msg));
+    let mut paths = opts.paths.clone();
+    let mut left: Option<HashSet<Oid>> = None;
+    // 시작점 수
+    let mut pushed = 0;
+    for rev in &opts.revs {
+        if let Some(hidden) = rev.strip_prefix('^') {
+            revwalk.hide(repo.revparse_single(hidden)?.peel_to_commit()?.id())?;
+            continue;
+        }
+        let spec = match repo.revparse(rev) {
+            Ok(spec) => spec,
+            // git 처럼 리비전이 아니고 파일이 있으면 경로로 본다
+            Err(_) if Path::new(rev).exists() => {
+                paths.push(rev.clone());
+                continue;
+            }
+            Err(_) => {
+                return Err(Error::from_str(&format!(
+                    "알 수 없는 리비전 또는 경로: {}",
+                    rev
+                )));
+            }
+        };
+
+        let commit_of = |object: Option<&git2::Object>| match object {
+            Some(object) => object.peel_to_commit().map(|commit| commit.id()),
+            None => repo.head()?.peel_to_commit().map(|commit| commit.id()),
+        };
+        let mode = spec.mode();
+        if mode.contains(RevparseMode::MERGE_BASE) {
+            // A...B: 둘 다 넣고 공통 조상부터는 숨김
+            let (a, b) = (commit_of(spec.from())?, commit_of(spec.to())?);
+            revwalk.push(a)?;
+            revwalk.push(b)?;
+            // criss-cross 머지에서는 공통 조상이 여럿이라 전부 숨긴다
+            if let Ok(bases) = repo.merge_bases(a, b) {
+                for base in bases.iter() {
+                    revwalk.hide(*base)?;
+                }
+            }
+
+            let mut side = repo.revwalk()?;
+            side.push(a)?;
+            side.hide(b)?;
+            left.get_or_insert_default()
+                .extend(side.collect::<Result<Vec<_>, _>>()?);
+            pushed += 2;
+        } else if mode.contains(RevparseMode::RANGE) {
+            revwalk.hide(commit_of(spec.from())?)?;
+            revwalk.push(commit_of(spec.to())?)?;
+            pushed += 1;
+        } else {
+            revwalk.push(commit_of(spec.from())?)?;
+            pushed += 1;
+        }
+    }
+
+    if opts.all {
+        // HEAD 가 없는(unborn) 저장소도 있으니 실패는 무시한다
+        let _ = revwalk.push_head();
+        revwalk.push_glob("refs/*")?;
+        pushed += 2;
+    }
+    if opts.branches {
+        revwalk.push_glob("refs/heads")?;
+        pushed += 2;
+    }
+    if pushed == 0 {
+        revwalk.push_head()?;
+    }
+
+    // 그래프는 자식이 항상 부모보다 먼저 나와야 하고, 시작점이 여럿이면 git 처럼 최신 커밋부터.
+    // (TIME 만으로는 같은 시각의 부모가 자식보다 먼저 나올 수 있다)
+    if opts.graph || opts.topo_order || pushed > 1 {
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    }
+    Ok(Revisions {
+        revwalk,
+        paths,
+        left,
+    })
+}
+
+// 모든 커밋을 조건에 맞는지와 함께 꺼낸다.
+// --skip 으로 건너뛴 커밋도 false 로 나오고, -n 개를 채우면 멈춘다
+fn walk<'r>(
+    repo: &'r Repository,
+    revwalk: Revwalk<'r>,
+    filter: Filter,
+    opts: &LogOptions,
+) -> impl Iterator<Item = Result<(Commit<'r>, bool), Error>> + 'r {
+    let skip = opts.skip;
+    let limit = skip.saturating_add(opts.max_count.unwrap_or(usize::MAX));
+    let mut matched = 0;
+    revwalk.map_while(move |oid| {
+        if matched >= limit {
+            return None;
+        }
+        let commit = match oid.and_then(|oid| repo.find_commit(oid)) {
+            Ok(commit) => commit,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(filter.matches(repo, &commit).map(|found| {
+            if found {
+                matched += 1;
+            }
+            (commit, found && matched > skip)
+        }))
+    })
+}
+
+// 시작점부터 조건에 맞는 커밋을 하나씩 꺼내는 iterator
+pub fn walk_commits<'r>(
+    repo: &'r Repository,
+    opts: &LogOptions,
+) -> Result<impl Iterator<Item = Result<Commit<'r>, Error>> + 'r, Error> {
+    let revs = revisions(repo, opts)?;
+    let filter = Filter::new(opts, revs.paths)?;
+    Ok(
+        walk(repo, revs.revwalk, filter, opts).filter_map(|commit| match commit {
+            Ok((commit, true)) => Some(Ok(commit)),
+            Ok((_, false)) => None,
+            Err(e) => Some(Err(e)),
+        }),
+    )
+}
+
+// 한 줄씩 만들어지는 대로 out 으로 넘긴다
+pub fn git_log(opts: &LogOptions, mut out: impl FnMut(String)) -> Result<(), Error> {
+    let repo = Repository::open(".")?;
+    let revs = revisions(&repo, opts)?;
+    let left = revs.left;
+    let filter = Filter::new(opts, revs.paths)?;
+    let commits = walk(&repo, revs.revwalk, filter, opts);
+
+    // A...B 에서는 커밋이 어느 쪽에서 왔는지 < / > 로 표시한다
+    let mark = |oid: Oid| match &left {
+        Some(left) if left.contains(&oid) => Some('<'),
+        Some(_) => Some('>'),
+        None => None,
+    };
+    let decorations = decorations(&repo, opts.color)?;
+    // --graph 의 기본 형식은 oneline
+    let format = opts
+        .format
+        .clone()
+        .or(opts.graph.then_some(Format::Oneline));
+    let pretty = format.as_ref().map(|format| Pretty {
+        format,
+        date: &opts.date,
+        color: opts.color,
+    });
+    let mut graph = opts.graph.then(|| Graph::new(opts.color));
+    let mut first = true;
+
+    for commit in commits {
+        let (commit, shown) = commit?;
+        let mark = mark(commit.id());
+        // 그래프는 출력하지 않는 커밋도 lane 이 끊기지 않도록 넣어야 한다
+        let (row, rows) = match &mut graph {
+            Some(graph) => {
+                let parents: Vec<Oid> = commit.parent_ids().collect();
+                graph.next(commit.id(), &parents, mark.unwrap_or('*'))
+            }
+            None => (
+                mark.map(|mark| format!("{} ", mark)).unwrap_or_default(),
+                Vec::new(),
+            ),
+        };
+
+        if shown {
+            let lines = match &pretty {
+                Some(pretty) => pretty.lines(
+                    &commit,
+                    decorations.get(&commit.id()).map_or(&[], Vec::as_slice),
+                ),
+                // 형식을 지정하지 않으면 "<hash>: <subject>"
+                None => vec![format!("{}: {}", commit.id(), pretty::subject(&commit))],
+            };
+            // 둘째 줄부터 앞에 붙일 그래프, 커밋 자리는 부모가 있으면 계속 이어진다
+            let pad = match graph {
+                Some(_) if commit.parent_count() > 0 => row.replace(['*', '<', '>'], "|"),
+                Some(_) => row.replace(['*', '<', '>'], " "),
+                None => String::new(),
+            };
+            let continued = |line: &str| {
+                if line.is_empty() {
+                    pad.trim_end().to_string()
+                } else {
+                    format!("{}{}", pad, line)
+                }
+            };
+
+            if !first && format.as_ref().is_some_and(Format::separated) {
+                out(continued(""));
+            }
+            first = false;
+            let mut lines = lines.into_iter();
+            out(format!("{}{}", row, lines.next().unwrap_or_default()));
+            if opts.show_signature {
+                out(continued(&format!(
+                    "    {}",
+                    signing::verify_commit(&repo, commit.id())?
+                )));
+            }
+            lines.for_each(|line| out(continued(&line)));
+        }
+        rows.into_iter().for_each(&mut out);
+    }
+    Ok(())
+}
+
+// 커밋별 "HEAD -> main", "tag: v1", "origin/main" 같은 표시
+pub(crate) fn decorations(
+    repo: &Repository,
+    color: bool,
+) -> Result<HashMap<Oid, Vec<String>>, Error> {
+    let mut res: HashMap<Oid, Vec<String>> = HashMap::new();
+    let head = repo.head().ok();
+    let head_branch = head
+        .as_ref()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(str::to_string));
+    if let Some(head) = &head
+        && head_branch.is_none()
+        && let Some(oid) = head.target()
+    {
+        res.entry(oid)
+            .or_default()
+            .push(paint(color, "1;36", "HEAD"));
+    }
+
+    for reference in repo.references()? {
+        let reference = reference?;
+        let (Some(name), Ok(commit)) = (reference.shorthand(), reference.peel_to_commit()) else {
+            continue;
+        };
+        let label = if reference.is_tag() {
+            paint(color, "1;33", &format!("tag: {}", name))
+        } else if reference.is_remote() {
+            paint(color, "1;31", name)
+        } else if !reference.is_branch() {
+            continue;
+        } else if head_branch.as_deref() == Some(name) {
+            format!(
+                "{}{}",
+                paint(color, "1;36", "HEAD -> "),
+                paint(color, "1;32", name)
+            )
+        } else {
+            paint(color, "1;32", name)
+        };
+
+        let labels = res.entry(commit.id()).or_default();
+        // HEAD 가 가리키는 브랜치는 맨 앞에
+        if head_branch.as_deref() == Some(name) {
+            labels.insert(0, label);
+        } else {
+            labels.push(label);
         }
     }
     Ok(res)
@@ -18,12 +427,20 @@ pub fn git_log() -> Result<Vec<String>, git2::Error> {
 
 #[cfg(test)]
 mod tests {
-    use crate::commands;
-    use crate::test_helpers::get_repo;
+    use super::*;
+    use crate::commands::{self, CommitOptions};
+    use crate::identity::IdentityOverride;
+    use crate::test_helpers::{get_repo, merge_history};
     use serial_test::serial;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::path::Path;
 
+    fn collect_log(opts: &LogOptions) -> Vec<String> {
+        let mut logs = Vec::new();
+        commands::git_log(opts, |line| logs.push(line)).expect("failed to get log");
+        logs
+    }
+
     #[test]
     #[serial]
     fn test_git_log() {
@@ -38,10 +455,339 @@ mod tests {
 
         commands::git_commit(commit_msg).expect("failed to commit");
 
-        let logs = commands::git_log().expect("failed to get log");
+        let logs = collect_log(&LogOptions::default());
         assert!(
             logs.first().unwrap().contains(commit_msg),
             "커밋 로그가 다름"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_git_log_trailer_filter() {
+        let _repo = get_repo();
+
+        fs::write("trailer.txt", "reviewed").expect("failed to write file");
+        commands::git_add("trailer.txt").expect("failed to add file");
+        let opts = CommitOptions {
+            messages: vec!["reviewed commit".to_string()],
+            trailers: vec![(
+                "Reviewed-by".to_string(),
+                "Alice <alice@example.com>".to_string(),
+            )],
+            signoff: true,
+            ..Default::default()
+        };
+        commands::git_commit_with(&opts).expect("failed to commit");
+
+        let filter = |key: &str, value: Option<&str>| LogOptions {
+            trailer: Some((key.to_string(), value.map(str::to_string))),
+            ..Default::default()
+        };
+        let logs = collect_log(&filter("reviewed-by", Some("alice")));
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("reviewed commit"));
+        assert_eq!(collect_log(&filter("Signed-off-by", None)).len(), 1);
+        assert!(collect_log(&filter("Reviewed-by", Some("bob"))).is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_log_filters() {
+        let _repo = get_repo();
+
+        let commit = |path: &str, msg: &str, author: &str, when: &str| {
+            fs::write(path, msg).expect("failed to write file");
+            commands::git_add(path).expect("failed to add file");
+            let opts = CommitOptions {
+                messages: vec![msg.to_string()],
+                identity: IdentityOverride {
+                    author: Some(author.to_string()),
+                    date: Some(when.to_string()),
+                },
+                ..Default::default()
+            };
+            commands::git_commit_with(&opts).expect("failed to commit");
+        };
+        commit(
+            "filter-a.txt",
+            "filter: first",
+            "Kim <kim@example.com>",
+            "@1000000000",
+        );
+        commit(
+            "filter-b.txt",
+            "filter: second",
+            "Lee <lee@example.com>",
+            "@1000000100",
+        );
+        commit(
+            "filter-a.txt",
+            "filter: third",
+            "Kim <kim@example.com>",
+            "@1000000200",
+        );
+
+        let logs = collect_log(&LogOptions {
+            author: Some("^kim".to_string()),
+            ignore_case: true,
+            grep: vec!["^filter:".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].contains("third") && logs[1].contains("first"));
+
+        let logs = collect_log(&LogOptions {
+            paths: vec!["filter-b.txt".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("second"));
+
+        let logs = collect_log(&LogOptions {
+            grep: vec!["filter".to_string()],
+            skip: 1,
+            max_count: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("second"));
+
+        // 커미터 날짜는 지금이므로 until 을 과거로 두면 아무것도 나오지 않는다
+        let logs = collect_log(&LogOptions {
+            until: Some("2 years ago".to_string()),
+            ..Default::default()
+        });
+        assert!(logs.is_empty());
+        let logs = collect_log(&LogOptions {
+            since: Some("1 hour ago".to_string()),
+            grep: vec!["filter".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 3);
+
+        assert!(
+            commands::git_log(
+                &LogOptions {
+                    grep: vec!["(".to_string()],
+                    ..Default::default()
+                },
+                |_| {}
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_log_graph() {
+        let (_dir, [root, side, main, merge]) = merge_history();
+        let short = |oid: Oid| oid.to_string()[..7].to_string();
+        let logs = collect_log(&LogOptions {
+            graph: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            logs,
+            [
+                format!("* {} (HEAD -> main) merge", short(merge)),
+                "|\\".to_string(),
+                format!("| * {} (side, tag: v1) side", short(side)),
+                format!("* | {} main", short(main)),
+                "|/".to_string(),
+                format!("* {} root", short(root)),
+            ]
+        );
+
+        // 걸러진 커밋도 lane 은 유지된다
+        let logs = collect_log(&LogOptions {
+            graph: true,
+            grep: vec!["^(side|root)$".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(logs[0], "|\\");
+        assert_eq!(logs.len(), 4);
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_log_revisions() {
+        let (_dir, [root, side, main, merge]) = merge_history();
+        let log = |revs: &[&str]| {
+            collect_log(&LogOptions {
+                revs: revs.iter().map(|rev| rev.to_string()).collect(),
+                ..Default::default()
+            })
+        };
+        let line = |prefix: &str, oid: Oid, msg: &str| format!("{}{}: {}", prefix, oid, msg);
+
+        assert!(log(&["main..side"]).is_empty());
+        assert_eq!(log(&["HEAD~2..main"]).len(), 3);
+        assert_eq!(
+            log(&["HEAD~1"]),
+            [line("", main, "main"), line("", root, "root")]
+        );
+        assert_eq!(log(&["side", "^HEAD~1"]), [line("", side, "side")]);
+        assert_eq!(
+            log(&["main~1...v1"]),
+            [line("> ", side, "side"), line("< ", main, "main")]
+        );
+        let missing = LogOptions {
+            revs: vec!["no-such-rev".to_string()],
+            ..Default::default()
+        };
+        assert!(commands::git_log(&missing, |_| {}).is_err());
+
+        let logs = collect_log(&LogOptions {
+            all: true,
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 4);
+        assert_eq!(logs[0], line("", merge, "merge"));
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_log_multiple_starts() {
+        let (_dir, [root, side, main, merge]) = merge_history();
+        let repo = Repository::open(".").unwrap();
+        // side 와 같은 시각에 만든 자식 커밋, HEAD 에서 닿지 않는 브랜치
+        let sig =
+            git2::Signature::new("agent", "agent@example.com", &git2::Time::new(3, 0)).unwrap();
+        let tree = repo.find_commit(side).unwrap().tree().unwrap();
+        let parent = repo.find_commit(side).unwrap();
+        let topic = repo
+            .commit(
+                Some("refs/heads/topic"),
+                &sig,
+                &sig,
+                "topic",
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+        let oids = |opts: &LogOptions| {
+            collect_log(opts)
+                .iter()
+                .map(|line| Oid::from_str(&line[..40]).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // 시작점이 여럿이어도 자식이 부모보다 먼저 나온다
+        let logs = oids(&LogOptions {
+            revs: vec!["topic".to_string(), "main~1".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(logs, [topic, side, main, root]);
+
+        // --all 은 HEAD 에서 닿지 않는 브랜치도 포함한다
+        let logs = oids(&LogOptions {
+            all: true,
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 5);
+        assert_eq!(logs[0], merge);
+        assert!(logs.iter().position(|&o| o == topic) < logs.iter().position(|&o| o == side));
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_log_symmetric_criss_cross() {
+        let (_dir, [_, side, main, _]) = merge_history();
+        let repo = Repository::open(".").unwrap();
+        // main 과 side 를 서로 반대로 머지해 공통 조상이 둘인 히스토리를 만든다
+        let sig = repo.signature().unwrap();
+        let (main_commit, side_commit) = (
+            repo.find_commit(main).unwrap(),
+            repo.find_commit(side).unwrap(),
+        );
+        let tree = main_commit.tree().unwrap();
+        let cross = |name: &str, parents: [&git2::Commit; 2]| {
+            repo.commit(
+                Some(&format!("refs/heads/{}", name)),
+                &sig,
+                &sig,
+                name,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+        };
+        let x = cross("x", [&main_commit, &side_commit]);
+        let y = cross("y", [&side_commit, &main_commit]);
+        assert_eq!(repo.merge_bases(x, y).unwrap().len(), 2);
+
+        let logs = collect_log(&LogOptions {
+            revs: vec!["x...y".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(logs.len(), 2);
+        assert!(
+            logs.iter()
+                .any(|line| line.starts_with(&format!("< {}", x)))
+        );
+        assert!(
+            logs.iter()
+                .any(|line| line.starts_with(&format!("> {}", y)))
+        );
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_log_formats() {
+        let (_dir, [_, side, _, merge]) = merge_history();
+        // 메시지가 비었거나 UTF-8 이 아닌 커밋
+        let repo = Repository::open(".").unwrap();
+        let sig = repo.signature().unwrap();
+        let tree = repo.find_commit(merge).unwrap().tree().unwrap();
+        let parent = repo.find_commit(merge).unwrap();
+        let empty = repo
+            .commit(Some("HEAD"), &sig, &sig, "", &tree, &[&parent])
+            .unwrap();
+
+        let logs = collect_log(&LogOptions::default());
+        assert_eq!(logs.len(), 5);
+        assert_eq!(logs[0], format!("{}: ", empty));
+
+        let logs = collect_log(&LogOptions {
+            format: Some(Format::Medium),
+            date: DateFormat::Unix,
+            revs: vec!["HEAD~1".to_string(), "^HEAD~2".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(
+            logs,
+            [
+                format!("commit {}", merge),
+                logs[1].clone(),
+                "Author: agent <agent@example.com>".to_string(),
+                "Date:   4".to_string(),
+                String::new(),
+                "    merge".to_string(),
+                String::new(),
+                format!("commit {} (side, tag: v1)", side),
+                "Author: agent <agent@example.com>".to_string(),
+                "Date:   3".to_string(),
+                String::new(),
+                "    side".to_string(),
+            ]
+        );
+        assert!(logs[1].starts_with("Merge: "));
+
+        let logs = collect_log(&LogOptions {
+            format: Some(Format::Custom("%h%d %an %s".to_string())),
+            max_count: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            logs[0],
+            format!("{} (HEAD -> main) agent ", &empty.to_string()[..7])
+        );
+        assert_eq!(logs[1], format!("{} agent merge", &merge.to_string()[..7]));
+        get_repo();
+    }
 }
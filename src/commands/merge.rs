@@ -1,69 +1,227 @@
-use git2::{MergeOptions, Repository};
+use crate::commands::checkout::{self, CheckoutOptions};
+use crate::commands::commit::{create_commit, update_head};
+use crate::commands::reset;
+use crate::hooks;
+use crate::identity::{self, IdentityOverride};
+use crate::signing;
+use crate::state;
+use git2::{Commit, Oid, Repository, RepositoryState};
 use std::io::BufRead;
 use std::{fs, io};
 
+const MERGE_MESSAGE: &str = "Merge commit";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum FastForward {
+    // fast-forward 할 수 있으면 하고, 아니면 머지 커밋을 만든다
+    #[default]
+    Allow,
+    // --ff-only: fast-forward 할 수 없으면 중단
+    Only,
+    // --no-ff: fast-forward 할 수 있어도 머지 커밋을 만든다
+    Never,
+}
+
 pub fn git_merge(branch: &str) -> Result<(), git2::Error> {
+    git_merge_with(branch, FastForward::default())
+}
+
+pub fn git_merge_with(branch: &str, ff: FastForward) -> Result<(), git2::Error> {
     let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
 
     // 대상 브랜치의 annotatedCommit 가져오기
     let branch_ref = repo.find_branch(branch, git2::BranchType::Local)?;
     let branch_commit = branch_ref.get().peel_to_commit()?;
     let annotated_commit = repo.find_annotated_commit(branch_commit.id())?;
 
-    let mut merge_opts = MergeOptions::new();
+    let (analysis, _) = repo.merge_analysis(&[&annotated_commit])?;
+    if analysis.is_up_to_date() {
+        println!("Already up to date.");
+        return Ok(());
+    }
+    if analysis.is_unborn() || (analysis.is_fast_forward() && ff != FastForward::Never) {
+        return fast_forward(&repo, branch, &branch_commit);
+    }
+    if ff == FastForward::Only {
+        return Err(git2::Error::from_str(&format!(
+            "'{}' 로 fast-forward 할 수 없음 (--ff-only)",
+            branch
+        )));
+    }
+
+    // --abort 로 돌아갈 위치
+    let head_commit = repo.head()?.peel_to_commit()?;
+    repo.reference(
+        "ORIG_HEAD",
+        head_commit.id(),
+        true,
+        "merge: updating ORIG_HEAD",
+    )?;
+
+    let mut merge_opts = git2::MergeOptions::new();
     // merge 수행 (워킹 디렉토리와 index에 결과가 반영됨)
     repo.merge(&[&annotated_commit], Some(&mut merge_opts), None)?;
 
     // 충돌 여부 확인
-    let mut index = repo.index()?;
+    let index = repo.index()?;
     if index.has_conflicts() {
         println!("충돌 파일 목록:");
-        for conflict in index.conflicts()? {
-            if let Ok(conflict) = conflict {
-                if let Some(entry) = conflict.our {
-                    let path = String::from_utf8_lossy(&entry.path).to_string();
-                    println!("* {path}");
-
-                    if let Ok(file_content) = fs::read_to_string(&path) {
-                        let reader = io::Cursor::new(&file_content);
-                        for (line_no, line) in reader.lines().enumerate() {
-                            if let Ok(line) = line {
-                                println!("{line_no}: {line}");
-                            }
+        for conflict in index.conflicts()?.flatten() {
+            if let Some(entry) = conflict.our {
+                let path = String::from_utf8_lossy(&entry.path).to_string();
+                println!("* {path}");
+
+                if let Ok(file_content) = fs::read_to_string(&path) {
+                    let reader = io::Cursor::new(&file_content);
+                    for (line_no, line) in reader.lines().enumerate() {
+                        if let Ok(line) = line {
+                            println!("{line_no}: {line}");
                         }
-                    } else {
-                        println!("파일 읽기 실패");
                     }
+                } else {
+                    println!("파일 읽기 실패");
                 }
             }
         }
-        return Err(git2::Error::from_str("머지 충돌 발생"));
+        // MERGE_HEAD 가 남은 채 머지 중 상태로 멈춘다. --continue 에서 쓸 메시지
+        fs::write(repo.path().join("MERGE_MSG"), MERGE_MESSAGE)
+            .map_err(|e| git2::Error::from_str(&format!("MERGE_MSG 작성 실패: {}", e)))?;
+        return Err(git2::Error::from_str(
+            "머지 충돌 발생. 충돌을 해결하고 add 한 뒤 merge --continue (취소하려면 --abort)",
+        ));
     }
 
-    let tree_oid = index.write_tree()?;
+    commit_merge(
+        &repo,
+        MERGE_MESSAGE,
+        &[&head_commit, &branch_commit],
+        &format!("merge {}: Merge made by recursive.", branch),
+    )?;
+    repo.checkout_head(None)?;
+
+    // 인자는 squash 머지 여부 (0: 일반 머지)
+    let _ = hooks::run_hook(&repo, "post-merge", &["0"], None);
+    Ok(())
+}
+
+// 충돌을 해결하고 add 한 뒤 머지 커밋을 만든다
+pub fn git_merge_continue() -> Result<(), git2::Error> {
+    let mut repo = Repository::open(".")?;
+    ensure_merging(&repo)?;
+
+    // HEAD 와 MERGE_HEAD 에 기록된 커밋들이 부모가 된다
+    let mut merge_heads = Vec::new();
+    repo.mergehead_foreach(|oid| {
+        merge_heads.push(*oid);
+        true
+    })?;
+
+    let index = repo.index()?;
+    if index.has_conflicts() {
+        let mut paths = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                paths.push(format!("\t{}", String::from_utf8_lossy(&entry.path)));
+            }
+        }
+        return Err(git2::Error::from_str(&format!(
+            "충돌이 남아 있음:\n{}\n충돌을 해결하고 add 한 뒤 merge --continue (취소하려면 --abort)",
+            paths.join("\n")
+        )));
+    }
+
+    let mut parents = vec![repo.head()?.peel_to_commit()?];
+    for oid in merge_heads {
+        parents.push(repo.find_commit(oid)?);
+    }
+    let parents: Vec<&Commit> = parents.iter().collect();
+
+    let msg = repo.message().unwrap_or_else(|_| MERGE_MESSAGE.to_string());
+    let subject = msg.lines().next().unwrap_or("").to_string();
+    let oid = commit_merge(
+        &repo,
+        &msg,
+        &parents,
+        &format!("commit (merge): {}", subject),
+    )?;
+    println!("Merge commit created: {}", &oid.to_string()[..7]);
+    Ok(())
+}
+
+// 머지하기 전의 인덱스와 작업 트리(ORIG_HEAD)로 돌아간다
+pub fn git_merge_abort() -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    ensure_merging(&repo)?;
+
+    // add 하지 않은 로컬 변경은 남기고 되돌린다 (reset --merge)
+    let orig_head = repo.revparse_single("ORIG_HEAD")?.peel_to_commit()?;
+    reset::reset_merge(&repo, &orig_head)?;
+    repo.cleanup_state()?;
+    println!("merge 를 취소함");
+    Ok(())
+}
+
+fn ensure_merging(repo: &Repository) -> Result<(), git2::Error> {
+    match repo.state() {
+        RepositoryState::Merge => Ok(()),
+        _ => Err(git2::Error::from_str("진행 중인 merge 가 없음")),
+    }
+}
+
+// 인덱스로 머지 커밋을 만들고 HEAD 를 옮긴 뒤 머지 상태를 정리한다
+fn commit_merge(
+    repo: &Repository,
+    msg: &str,
+    parents: &[&Commit],
+    reflog_msg: &str,
+) -> Result<Oid, git2::Error> {
+    let tree_oid = repo.index()?.write_tree()?;
     let tree = repo.find_tree(tree_oid)?;
 
-    let head_commit = repo.head()?.peel_to_commit()?;
+    let author = identity::author_signature(repo, &IdentityOverride::default())?;
+    let committer = identity::committer_signature(repo)?;
+    let sign = signing::should_sign(repo, None, "commit.gpgSign");
+    let oid = create_commit(repo, &author, &committer, msg, &tree, parents, sign)?;
+    update_head(repo, oid, reflog_msg)?;
+    repo.cleanup_state()?;
+    Ok(oid)
+}
 
-    let sig = repo.signature()?;
-    repo.commit(
-        Some("HEAD"),
-        &sig,
-        &sig,
-        "Merge commit",
-        &tree,
-        &[&head_commit, &branch_commit],
+// 머지 커밋 없이 현재 브랜치(또는 커밋이 없는 브랜치)를 대상 커밋으로 옮긴다
+fn fast_forward(repo: &Repository, branch: &str, target: &Commit) -> Result<(), git2::Error> {
+    let head = repo.head().ok().and_then(|head| head.target());
+    // 로컬 변경 사항을 덮어쓰게 되면 checkout 처럼 중단한다
+    checkout::checkout_tree(repo, target.as_object(), &CheckoutOptions::default())?;
+
+    // reset --hard ORIG_HEAD 로 fast-forward 를 되돌릴 수 있게 남겨 둔다
+    if let Some(head) = head {
+        repo.reference("ORIG_HEAD", head, true, "merge: updating ORIG_HEAD")?;
+    }
+    update_head(
+        repo,
+        target.id(),
+        &format!("merge {}: Fast-forward", branch),
     )?;
 
-    repo.checkout_head(None)?;
+    if let Some(head) = head {
+        println!(
+            "Updating {}..{}",
+            &head.to_string()[..7],
+            &target.id().to_string()[..7]
+        );
+    }
+    println!("Fast-forward");
+    let _ = hooks::run_hook(repo, "post-merge", &["0"], None);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::commands;
+    use super::*;
+    use crate::commands::{self, ResetMode, TagOptions};
     use crate::test_helpers::{checkout, get_repo, write_dummy_add_commit};
-    use git2::build::CheckoutBuilder;
     use serial_test::serial;
     use std::fs;
 
@@ -87,7 +245,9 @@ mod tests {
         let main_branch = "main";
         checkout(&repo, main_branch).unwrap();
 
-        commands::git_merge(feature_branch).expect("failed to merge feature branch");
+        // fast-forward 할 수 있는 브랜치라 --no-ff 로 머지 커밋을 만든다
+        commands::git_merge_with(feature_branch, FastForward::Never)
+            .expect("failed to merge feature branch");
 
         let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
         assert_eq!(
@@ -124,18 +284,162 @@ mod tests {
 
         // main 브랜치에서 conflict.txt 수정 후 커밋 (충돌 발생 준비)
         fs::write(file_name, "GoodBye World").expect("failed to write main content");
-        // FIXME commit 하고 난 이후 conflict가 발생한다(의도한 동작)
-        // 그러나 repo.cleanup_state()로 클린업해도 git_merge_conflict 이후에 실행되는 테스트가 실패해버린다.
-        // commit 만 일단 안하면 이후의 테스트도 성공을 한다.
         commands::git_add(file_name).expect("failed to add main branch conflict.txt");
         commands::git_commit("main commit").expect("failed to commit main change");
 
         // conflict_branch를 main에 병합 -> 충돌이 발생해야 함
         let merge_result = commands::git_merge(branch_name);
         assert!(merge_result.is_err(), "merge 충돌이 발생하지 않음");
+        assert_eq!(repo.state(), git2::RepositoryState::Merge);
+
+        // --abort 로 머지 전으로 돌아가야 이후 테스트가 커밋할 수 있다
+        commands::git_merge_abort().expect("failed to abort merge");
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(fs::read_to_string(file_name).unwrap(), "GoodBye World");
+    }
+
+    #[test]
+    #[serial]
+    fn git_merge_fast_forward() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |path: &str, content: &str| {
+            fs::write(path, content).unwrap();
+            commands::git_add(path).unwrap();
+            commands::git_commit(content).unwrap()
+        };
+        let head = || repo.head().unwrap().target().unwrap().to_string();
+
+        let base = commit("a.txt", "a");
+        commands::git_create_branch("feature").unwrap();
+        checkout(&repo, "feature").unwrap();
+        let feature = commit("b.txt", "b");
+        checkout(&repo, "main").unwrap();
+
+        // --ff-only 로 fast-forward
+        git_merge_with("feature", FastForward::Only).unwrap();
+        assert_eq!(head(), feature);
+        assert_eq!(
+            repo.revparse_single("ORIG_HEAD").unwrap().id().to_string(),
+            base
+        );
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(fs::read_to_string("b.txt").unwrap(), "b");
+        let reflog = repo.reflog("refs/heads/main").unwrap();
+        assert_eq!(
+            reflog.get(0).unwrap().message().unwrap(),
+            "merge feature: Fast-forward"
+        );
+
+        // 이미 포함된 브랜치는 아무것도 하지 않는다
+        git_merge("feature").unwrap();
+        git_merge_with("feature", FastForward::Never).unwrap();
+        assert_eq!(head(), feature);
+
+        // 갈라진 브랜치는 --ff-only 로 머지할 수 없고, 기본은 머지 커밋
+        commands::git_create_branch("side").unwrap();
+        checkout(&repo, "side").unwrap();
+        commit("c.txt", "c");
+        checkout(&repo, "main").unwrap();
+        commit("d.txt", "d");
+        let main = head();
+        assert!(git_merge_with("side", FastForward::Only).is_err());
+        assert_eq!(head(), main);
+        git_merge("side").unwrap();
+        let merge = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(merge.parent_count(), 2);
+        assert_eq!(merge.parent_id(0).unwrap().to_string(), main);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(fs::metadata("c.txt").is_ok());
+
+        // --no-ff 는 fast-forward 할 수 있어도 머지 커밋
+        commands::git_checkout(&base).unwrap();
+        git_merge_with("feature", FastForward::Never).unwrap();
+        let merge = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(merge.parent_id(0).unwrap().to_string(), base);
+        assert_eq!(merge.parent_id(1).unwrap().to_string(), feature);
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn git_merge_continue_and_guard() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |path: &str, content: &str| {
+            fs::write(path, content).unwrap();
+            commands::git_add(path).unwrap();
+            commands::git_commit(content).unwrap()
+        };
+
+        commit("a.txt", "base\n");
+        commands::git_create_branch("side").unwrap();
+        checkout(&repo, "side").unwrap();
+        let side = commit("a.txt", "side\n");
+        checkout(&repo, "main").unwrap();
+        let main = commit("a.txt", "main\n");
+        repo.tag_lightweight("v0", &repo.revparse_single("HEAD").unwrap(), false)
+            .unwrap();
+
+        assert!(git_merge_continue().is_err());
+        assert!(git_merge_abort().is_err());
+        assert!(git_merge("side").is_err());
+        assert!(fs::read_to_string("a.txt").unwrap().contains("<<<<<<<"));
+
+        // 머지 중에는 커밋, 체크아웃, 다른 머지와 revert 를 막는다
+        let err = commands::git_commit("oops").unwrap_err();
+        assert!(err.message().contains("merge --continue"));
+        assert!(commands::git_checkout("side").is_err());
+        assert!(git_merge("side").is_err());
+        assert!(commands::git_revert("HEAD").is_err());
+        // HEAD 나 ref 를 바꾸는 명령도 막는다
+        for mode in [ResetMode::Soft, ResetMode::Mixed, ResetMode::Hard] {
+            assert!(commands::git_reset("HEAD~1", mode).is_err());
+        }
+        assert!(commands::git_create_branch("new").is_err());
+        assert!(commands::git_rename_branch(Some("side"), "renamed", false).is_err());
+        assert!(commands::git_copy_branch(Some("side"), "copied", false).is_err());
+        assert!(commands::git_delete_branch("side", true).is_err());
+        assert!(commands::git_create_tag("v1", None, &TagOptions::default()).is_err());
+        assert!(commands::git_delete_tag("v0").is_err());
+        assert!(commands::git_recover(&side, "rescued").is_err());
+        let err = commands::git_push("origin", "main").unwrap_err();
+        assert!(err.message().contains("merge --continue"));
+        assert_eq!(repo.branches(None).unwrap().count(), 2);
+        assert!(repo.find_reference("refs/tags/v0").is_ok());
+        assert_eq!(repo.head().unwrap().target().unwrap().to_string(), main);
+
+        // 충돌을 해결하지 않으면 계속할 수 없다
+        assert!(git_merge_continue().is_err());
+        fs::write("a.txt", "main\nside\n").unwrap();
+        commands::git_add("a.txt").unwrap();
+        git_merge_continue().unwrap();
+
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        let merge = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(merge.message().unwrap(), "Merge commit");
+        assert_eq!(merge.parent_id(0).unwrap().to_string(), main);
+        assert_eq!(merge.parent_id(1).unwrap().to_string(), side);
+        assert_eq!(
+            merge.tree().unwrap().get_name("a.txt").unwrap().id(),
+            repo.blob("main\nside\n".as_bytes()).unwrap()
+        );
+        commit("b.txt", "after merge");
 
-        let mut checkout_builder = CheckoutBuilder::new();
-        checkout_builder.force();
-        repo.checkout_head(Some(&mut checkout_builder)).unwrap();
+        // --abort 는 reset --merge 처럼 머지와 상관없는 로컬 변경을 남긴다
+        checkout(&repo, "side").unwrap();
+        commit("a.txt", "side2\n");
+        checkout(&repo, "main").unwrap();
+        let main = commit("a.txt", "main2\n");
+        fs::write("b.txt", "dirty").unwrap();
+        assert!(git_merge("side").is_err());
+        git_merge_abort().unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target().unwrap().to_string(), main);
+        assert_eq!(fs::read_to_string("a.txt").unwrap(), "main2\n");
+        assert_eq!(fs::read_to_string("b.txt").unwrap(), "dirty");
+        get_repo();
     }
 }
@@ -2,24 +2,44 @@ pub mod add;
 pub mod branch;
 pub mod checkout;
 pub mod commit;
+pub mod export;
 pub mod help;
 pub mod init;
+pub mod lint;
 pub mod log;
 pub mod merge;
 pub mod push;
+pub mod reflog;
 pub mod reset;
 pub mod restore;
 pub mod revert;
+pub mod shortlog;
+pub mod show;
+pub mod stats;
+pub mod tag;
 
 pub use add::git_add;
 pub use branch::*;
-pub use checkout::git_checkout;
-pub use commit::git_commit;
+pub use checkout::{
+    CheckoutOptions, git_checkout, git_checkout_new_branch, git_checkout_orphan, git_checkout_with,
+    git_switch,
+};
+pub use commit::{CommitOptions, git_commit, git_commit_with};
+pub use export::{ExportFormat, ExportOptions, git_export};
 pub use help::git_help;
 pub use init::git_init;
-pub use log::git_log;
-pub use merge::git_merge;
-pub use push::git_push;
-pub use reset::git_reset;
-pub use restore::git_restore;
-pub use revert::git_revert;
+pub use lint::git_lint;
+pub use log::{LogOptions, git_log};
+pub use merge::{FastForward, git_merge, git_merge_abort, git_merge_continue, git_merge_with};
+pub use push::{git_push, git_push_with};
+pub use reflog::{git_recover, git_recover_list, git_reflog};
+pub use reset::{ResetMode, git_reset, git_reset_paths};
+pub use restore::{RestoreOptions, git_restore, git_restore_with};
+pub use revert::{
+    RevertOptions, git_revert, git_revert_abort, git_revert_continue, git_revert_many,
+    git_revert_skip, git_revert_with,
+};
+pub use shortlog::{ShortlogOptions, git_shortlog};
+pub use show::git_show;
+pub use stats::git_stats;
+pub use tag::*;
@@ -1,18 +1,71 @@
-use git2::Repository;
+use crate::hooks;
+use crate::state;
+use git2::{Oid, Repository};
 
 pub fn git_push(remote_name: &str, refspec: &str) -> Result<(), git2::Error> {
+    git_push_with(remote_name, refspec, false)
+}
+
+pub fn git_push_with(remote_name: &str, refspec: &str, no_verify: bool) -> Result<(), git2::Error> {
     let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
     let mut remote = repo.find_remote(remote_name)?;
 
+    if !no_verify {
+        let url = remote.pushurl().or(remote.url()).unwrap_or("").to_string();
+        let input = pre_push_input(&repo, remote_name, refspec)?;
+        hooks::run_hook(&repo, "pre-push", &[remote_name, &url], Some(&input))?;
+    }
+
     remote.push(&[refspec], None)?;
     println!("push complete to remote: {}", remote_name);
     Ok(())
 }
 
+// pre-push 훅 stdin: "<local ref> <local sha> <remote ref> <remote sha>"
+fn pre_push_input(
+    repo: &Repository,
+    remote_name: &str,
+    refspec: &str,
+) -> Result<String, git2::Error> {
+    let spec = refspec.trim_start_matches('+');
+    let (src, dst) = spec.split_once(':').unwrap_or((spec, ""));
+
+    // ":dst" 형식은 원격 브랜치 삭제
+    let (local_ref, local_oid) = if src.is_empty() {
+        ("(delete)".to_string(), Oid::zero())
+    } else {
+        let (object, reference) = repo.revparse_ext(src)?;
+        let name = reference
+            .and_then(|r| r.name().map(str::to_string))
+            .unwrap_or_else(|| src.to_string());
+        (name, object.peel_to_commit()?.id())
+    };
+
+    let remote_ref = if dst.is_empty() {
+        local_ref.clone()
+    } else if dst.starts_with("refs/") {
+        dst.to_string()
+    } else {
+        format!("refs/heads/{}", dst)
+    };
+    let tracking = match remote_ref.strip_prefix("refs/heads/") {
+        Some(short) => format!("refs/remotes/{}/{}", remote_name, short),
+        None => remote_ref.clone(),
+    };
+    let remote_oid = repo.refname_to_id(&tracking).unwrap_or(Oid::zero());
+
+    Ok(format!(
+        "{} {} {} {}\n",
+        local_ref, local_oid, remote_ref, remote_oid
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::commands;
-    use git2::Repository;
+    use crate::test_helpers::write_hook;
+    use git2::{Oid, Repository};
     use serial_test::serial;
     use std::path::Path;
     use std::{env, fs};
@@ -76,4 +129,41 @@ mod tests {
             "remote 커밋 메시지가 다름"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_git_push_pre_push_hook() {
+        let remote_dir = TempDir::new().expect("failed to create temporary directory");
+        let remote_path = remote_dir.path().to_str().unwrap();
+        Repository::init_bare(remote_path).expect("failed to initialize bare repository");
+
+        let local_dir = TempDir::new().expect("failed to create temporary directory");
+        env::set_current_dir(local_dir.path()).expect("failed to set current directory");
+        let local_repo = Repository::init(".").expect("failed to initialize repository");
+        local_repo
+            .remote("origin", remote_path)
+            .expect("failed to remote");
+
+        fs::write("push.txt", "pre-push").expect("failed to write file");
+        commands::git_add("push.txt").expect("failed to add file");
+        let oid = commands::git_commit("pre-push commit").expect("failed to commit");
+
+        write_hook(
+            &local_repo,
+            "pre-push",
+            "echo \"$1 $2\" > .git/pre-push-args; cat > .git/pre-push-input; exit 1",
+        );
+        assert!(commands::git_push("origin", "refs/heads/main").is_err());
+
+        let args = fs::read_to_string(".git/pre-push-args").unwrap();
+        assert_eq!(args.trim(), format!("origin {}", remote_path));
+        let input = fs::read_to_string(".git/pre-push-input").unwrap();
+        assert_eq!(
+            input,
+            format!("refs/heads/main {} refs/heads/main {}\n", oid, Oid::zero())
+        );
+
+        // --no-verify 면 훅을 건너뛰고 push 된다
+        commands::git_push_with("origin", "refs/heads/main", true).expect("failed to push");
+    }
 }
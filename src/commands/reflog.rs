@@ -0,0 +1,164 @@
+use crate::date;
+use crate::state;
+use git2::{Error, ObjectType, Oid, Repository};
+use std::collections::HashSet;
+
+// reflog 를 git reflog 처럼 "<hash> <ref>@{n}: <message>" 로 (최신순)
+pub fn git_reflog(name: Option<&str>) -> Result<Vec<String>, Error> {
+    let repo = Repository::open(".")?;
+    let (refname, shown) = match name.unwrap_or("HEAD") {
+        "HEAD" | "@" => ("HEAD".to_string(), "HEAD".to_string()),
+        name => {
+            let reference = repo.resolve_reference_from_short_name(name)?;
+            let refname = reference
+                .name()
+                .ok_or_else(|| Error::from_str("ref 이름이 UTF-8 이 아님"))?
+                .to_string();
+            (refname, name.to_string())
+        }
+    };
+
+    let reflog = repo.reflog(&refname)?;
+    Ok(reflog
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            format!(
+                "{} {}@{{{}}}: {}",
+                &entry.id_new().to_string()[..7],
+                shown,
+                i,
+                entry.message().unwrap_or("")
+            )
+        })
+        .collect())
+}
+
+// 어떤 ref 에서도 닿지 않는 커밋 중, 다른 그런 커밋의 부모가 아닌 것 (잃어버린 줄기의 끝)
+pub fn dangling_commits(repo: &Repository) -> Result<Vec<Oid>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    let _ = revwalk.push_head();
+    revwalk.push_glob("refs/*")?;
+    let reachable: HashSet<Oid> = revwalk.collect::<Result<_, _>>()?;
+
+    let odb = repo.odb()?;
+    let mut unreachable = Vec::new();
+    odb.foreach(|&oid| {
+        if !reachable.contains(&oid) && matches!(odb.read_header(oid), Ok((_, ObjectType::Commit)))
+        {
+            unreachable.push(oid);
+        }
+        true
+    })?;
+
+    let mut parents = HashSet::new();
+    for &oid in &unreachable {
+        parents.extend(repo.find_commit(oid)?.parent_ids());
+    }
+    let mut dangling: Vec<Oid> = unreachable
+        .into_iter()
+        .filter(|oid| !parents.contains(oid))
+        .collect();
+    // 최근 커밋부터
+    dangling.sort_by_cached_key(|&oid| {
+        std::cmp::Reverse(
+            repo.find_commit(oid)
+                .map(|commit| commit.committer().when().seconds())
+                .unwrap_or(0),
+        )
+    });
+    Ok(dangling)
+}
+
+// 잃어버린 커밋 목록. HEAD reflog 에 남아 있으면 HEAD@{n} 도 같이 보여준다
+pub fn git_recover_list() -> Result<Vec<String>, Error> {
+    let repo = Repository::open(".")?;
+    let reflog = repo.reflog("HEAD")?;
+
+    let mut res = Vec::new();
+    for oid in dangling_commits(&repo)? {
+        let commit = repo.find_commit(oid)?;
+        let summary = String::from_utf8_lossy(commit.summary_bytes().unwrap_or_default());
+        let mut line = format!(
+            "{} {} {}",
+            &oid.to_string()[..7],
+            date::format_relative(&commit.committer().when()),
+            summary
+        );
+        if let Some(i) = reflog.iter().position(|entry| entry.id_new() == oid) {
+            line.push_str(&format!(" (HEAD@{{{}}})", i));
+        }
+        res.push(line);
+    }
+    Ok(res)
+}
+
+// 잃어버린 커밋(또는 HEAD@{n} 같은 아무 리비전)에 브랜치를 다시 만든다
+pub fn git_recover(rev: &str, branch_name: &str) -> Result<(), Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+    repo.branch(branch_name, &commit, false)?;
+    println!(
+        "branch '{}' created at {}",
+        branch_name,
+        &commit.id().to_string()[..7]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands;
+    use crate::test_helpers::{get_repo, write_dummy_add_commit};
+    use git2::{BranchType, ResetType};
+    use serial_test::serial;
+    use std::fs;
+
+    #[test]
+    #[serial]
+    fn test_reflog_and_recover() {
+        let repo = get_repo();
+        if repo.head().is_err() {
+            write_dummy_add_commit();
+        }
+
+        fs::write("lost.txt", "lost work").expect("failed to write file");
+        commands::git_add("lost.txt").expect("failed to add file");
+        let lost = commands::git_commit("lost commit").expect("failed to commit");
+        let lost = Oid::from_str(&lost).unwrap();
+
+        let reflog = git_reflog(None).unwrap();
+        assert!(reflog[0].starts_with(&format!(
+            "{} HEAD@{{0}}: commit: lost commit",
+            &lost.to_string()[..7]
+        )));
+
+        // 실수로 reset --hard 해서 커밋을 잃어버림
+        let parent = repo.find_commit(lost).unwrap().parent(0).unwrap();
+        repo.reset(parent.as_object(), ResetType::Hard, None)
+            .unwrap();
+        assert_eq!(repo.revparse_single("HEAD@{1}").unwrap().id(), lost);
+
+        let lost_list = git_recover_list().unwrap();
+        assert!(
+            lost_list
+                .iter()
+                .any(|line| line.starts_with(&lost.to_string()[..7])
+                    && line.contains("lost commit")
+                    && line.ends_with("(HEAD@{1})"))
+        );
+        assert!(dangling_commits(&repo).unwrap().contains(&lost));
+
+        git_recover("HEAD@{1}", "rescued").expect("failed to recover");
+        let rescued = repo.find_branch("rescued", BranchType::Local).unwrap();
+        assert_eq!(rescued.get().target(), Some(lost));
+        assert!(!dangling_commits(&repo).unwrap().contains(&lost));
+        assert!(
+            git_reflog(Some("rescued")).unwrap()[0].contains(" rescued@{0}: branch: Created from")
+        );
+
+        commands::git_delete_branch("rescued", true).unwrap();
+    }
+}
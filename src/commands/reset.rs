@@ -1,31 +1,182 @@
-use git2::{build::CheckoutBuilder, Error, Repository, ResetType};
+use crate::commands::restore;
+use crate::pretty;
+use crate::state;
+use git2::{Commit, Error, Repository, ResetType, Status, StatusOptions};
+use std::collections::HashSet;
+use std::fs;
 
-pub fn git_reset(path: &str, reset_type: &str) -> Result<(), Error> {
-    let repo = Repository::open(".")?;
-    let head = repo.head()?;
-    let head_commit = head.peel_to_commit()?;
-    let rt = match reset_type.to_lowercase().as_str() {
-        "soft" => ResetType::Soft,
-        "hard" => ResetType::Hard,
-        "mixed" | _ => ResetType::Mixed,
-    };
-
-    match rt {
-        ResetType::Soft => {
-            repo.reset(head_commit.as_object(), rt, None)?;
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ResetMode {
+    // HEAD(브랜치)만 옮긴다
+    Soft,
+    // 인덱스도 맞춘다. 작업 트리는 그대로
+    #[default]
+    Mixed,
+    // 인덱스와 작업 트리 모두 맞춘다
+    Hard,
+    // 바뀌는 파일에 로컬 변경이 있으면 중단, 나머지 로컬 변경은 유지
+    Keep,
+    // 바뀌는 파일에 add 하지 않은 변경이 있으면 중단. 충돌난 머지를 되돌릴 때 쓴다
+    Merge,
+}
+
+impl ResetMode {
+    pub fn parse(input: &str) -> Result<ResetMode, Error> {
+        match input.trim_start_matches("--") {
+            "soft" => Ok(ResetMode::Soft),
+            "mixed" => Ok(ResetMode::Mixed),
+            "hard" => Ok(ResetMode::Hard),
+            "keep" => Ok(ResetMode::Keep),
+            "merge" => Ok(ResetMode::Merge),
+            _ => Err(Error::from_str(&format!(
+                "알 수 없는 reset 모드: {} (soft, mixed, hard, keep, merge)",
+                input
+            ))),
         }
-        ResetType::Mixed => {
-            let mut checkout_opts = CheckoutBuilder::new();
-            checkout_opts.path(path);
-            repo.reset(head_commit.as_object(), rt, Some(&mut checkout_opts))?;
+    }
+}
+
+// reset [--soft|--mixed|--hard|--keep|--merge] <rev>. 현재 브랜치(또는 detached HEAD)를 rev 로 옮긴다
+pub fn git_reset(rev: &str, mode: ResetMode) -> Result<(), Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    let target = repo.revparse_single(rev)?.peel_to_commit()?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    match mode {
+        ResetMode::Soft => repo.reset(target.as_object(), ResetType::Soft, None)?,
+        ResetMode::Mixed => repo.reset(target.as_object(), ResetType::Mixed, None)?,
+        ResetMode::Hard => repo.reset(target.as_object(), ResetType::Hard, None)?,
+        ResetMode::Keep | ResetMode::Merge => reset_keeping(&repo, &head, &target, mode)?,
+    }
+    // merge --abort 처럼 이전 위치로 돌아갈 수 있게 남겨 둔다
+    repo.reference("ORIG_HEAD", head.id(), true, "reset: updating ORIG_HEAD")?;
+
+    if mode != ResetMode::Soft && mode != ResetMode::Mixed {
+        println!(
+            "HEAD is now at {} {}",
+            &target.id().to_string()[..7],
+            pretty::subject(&target)
+        );
+    }
+    Ok(())
+}
+
+// reset <rev> -- <path>... HEAD 는 그대로 두고 인덱스의 해당 경로만 rev 의 내용으로 (작업 트리는 그대로)
+pub fn git_reset_paths(rev: &str, paths: &[&str]) -> Result<(), Error> {
+    if paths.is_empty() {
+        return Err(Error::from_str("reset 할 경로가 없음"));
+    }
+    let repo = Repository::open(".")?;
+    let target = repo.revparse_single(rev)?.peel_to_commit()?;
+    let tree = target.tree()?;
+    let index = repo.index()?;
+    for path in paths {
+        if !restore::matches_tree(&tree, path)? && !restore::matches_index(&index, path)? {
+            return Err(Error::from_str(&format!(
+                "'{}' 경로가 {} 와 인덱스에 없음",
+                path, rev
+            )));
         }
-        ResetType::Hard => {
-            let mut checkout_opts = CheckoutBuilder::new();
-            checkout_opts.path(path);
-            checkout_opts.force();
-            repo.reset(head_commit.as_object(), rt, Some(&mut checkout_opts))?;
+    }
+    repo.reset_default(Some(target.as_object()), paths.iter())?;
+    Ok(())
+}
+
+// merge --abort, revert --abort/--skip 에서 쓰는 reset --merge. add 하지 않은 로컬 변경은 남긴다
+pub(crate) fn reset_merge(repo: &Repository, target: &Commit) -> Result<(), Error> {
+    let head = repo.head()?.peel_to_commit()?;
+    reset_keeping(repo, &head, target, ResetMode::Merge)
+}
+
+// --keep, --merge. hard reset 하되 지켜야 할 로컬 변경은 그대로 작업 트리에 남긴다
+fn reset_keeping(
+    repo: &Repository,
+    head: &Commit,
+    target: &Commit,
+    mode: ResetMode,
+) -> Result<(), Error> {
+    // HEAD 와 target 사이에 달라지는 경로
+    let diff = repo.diff_tree_to_tree(Some(&head.tree()?), Some(&target.tree()?), None)?;
+    let changing: HashSet<String> = diff
+        .deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+
+    let mut status_opts = StatusOptions::new();
+    status_opts.include_untracked(false);
+    let statuses = repo.statuses(Some(&mut status_opts))?;
+
+    let unstaged = Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_TYPECHANGE;
+    let mut keep = Vec::new();
+    let mut blocked = Vec::new();
+    for entry in statuses.iter() {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        let status = entry.status();
+        let keeps = match mode {
+            // 로컬 변경이 있는 파일은 모두 남기되, 바뀌는 파일이면 중단
+            ResetMode::Keep => {
+                if changing.contains(path) {
+                    blocked.push(path.to_string());
+                }
+                true
+            }
+            // 충돌난 파일과 add 한 변경은 되돌리고, add 하지 않은 변경은 남긴다
+            _ => {
+                if status.is_conflicted() || !status.intersects(unstaged) {
+                    false
+                } else {
+                    if changing.contains(path)
+                        || status.intersects(
+                            Status::INDEX_NEW | Status::INDEX_MODIFIED | Status::INDEX_DELETED,
+                        )
+                    {
+                        blocked.push(path.to_string());
+                    }
+                    true
+                }
+            }
+        };
+        if keeps {
+            keep.push(path.to_string());
         }
     }
+    if !blocked.is_empty() {
+        return Err(Error::from_str(&format!(
+            "다음 파일에 로컬 변경 사항이 있어 reset 할 수 없음:\n{}",
+            blocked
+                .iter()
+                .map(|path| format!("\t{}", path))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )));
+    }
+
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| Error::from_str("bare 저장소에서는 reset --keep/--merge 불가"))?;
+    let saved: Vec<(String, Option<Vec<u8>>)> = keep
+        .into_iter()
+        .map(|path| {
+            let content = fs::read(workdir.join(&path)).ok();
+            (path, content)
+        })
+        .collect();
+
+    repo.reset(target.as_object(), ResetType::Hard, None)?;
+
+    for (path, content) in saved {
+        let full = workdir.join(&path);
+        let res = match content {
+            Some(content) => fs::write(&full, content),
+            None => fs::remove_file(&full),
+        };
+        res.map_err(|e| Error::from_str(&format!("{} 복원 실패: {}", path, e)))?;
+    }
     Ok(())
 }
 
@@ -57,7 +208,7 @@ mod tests {
         commands::git_add(file_name).expect("파일 stage 실패");
 
         // soft reset: 인덱스와 워킹 트리 모두 그대로 유지해야 함
-        git_reset(file_name, "soft").expect("soft reset 실패");
+        git_reset("HEAD", ResetMode::Soft).expect("soft reset 실패");
 
         // 인덱스의 엔트리와 비교하지 않습니다. soft reset은 인덱스를 건드리지 않음
         let working_content = fs::read_to_string(file_name).expect("파일 읽기 실패");
@@ -86,7 +237,7 @@ mod tests {
         commands::git_add(file_name).expect("파일 stage 실패");
 
         // mixed reset: 인덱스는 초기 상태로, 워킹 디렉토리는 수정 내용 유지
-        git_reset(file_name, "mixed").expect("mixed reset 실패");
+        git_reset_paths("HEAD", &[file_name]).expect("mixed reset 실패");
 
         let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
         let head_tree = head_commit.tree().unwrap();
@@ -126,7 +277,7 @@ mod tests {
         commands::git_add(file_name).expect("파일 stage 실패");
 
         // hard reset: 인덱스와 워킹 디렉토리 모두 HEAD 상태로 복원
-        git_reset(file_name, "hard").expect("hard reset 실패");
+        git_reset("HEAD", ResetMode::Hard).expect("hard reset 실패");
 
         let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
         let head_tree = head_commit.tree().unwrap();
@@ -147,4 +298,75 @@ mod tests {
             "워킹 디렉토리 내용이 변경됨"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_git_reset_modes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |path: &str, content: &str| {
+            fs::write(path, content).unwrap();
+            commands::git_add(path).unwrap();
+            commands::git_commit(content).unwrap()
+        };
+        let head = || repo.head().unwrap().target().unwrap().to_string();
+        let read = |path: &str| fs::read_to_string(path).unwrap();
+
+        let first = commit("a.txt", "a1");
+        let second = commit("b.txt", "b1");
+        let third = commit("a.txt", "a2");
+
+        // 모르는 모드는 mixed 로 넘어가지 않고 거부한다
+        assert!(ResetMode::parse("--hrad").is_err());
+        assert_eq!(ResetMode::parse("--keep").unwrap(), ResetMode::Keep);
+
+        // --soft 는 브랜치만 옮기고 인덱스는 그대로 (a2 가 staged 로 남는다)
+        git_reset("HEAD~2", ResetMode::Soft).unwrap();
+        assert_eq!(head(), first);
+        assert_eq!(repo.refname_to_id("ORIG_HEAD").unwrap().to_string(), third);
+        let status = repo.status_file(Path::new("a.txt")).unwrap();
+        assert_eq!(status, git2::Status::INDEX_MODIFIED);
+
+        // --hard 로 되돌아간다 (ORIG_HEAD 사용)
+        git_reset("ORIG_HEAD", ResetMode::Hard).unwrap();
+        assert_eq!(head(), third);
+        assert_eq!(read("a.txt"), "a2");
+
+        // --keep: 바뀌는 파일(a.txt)에 로컬 변경이 있으면 중단
+        fs::write("a.txt", "local").unwrap();
+        assert!(git_reset("HEAD~1", ResetMode::Keep).is_err());
+        assert_eq!(head(), third);
+
+        // 바뀌지 않는 파일(b.txt)의 로컬 변경은 남긴다
+        git_reset("HEAD", ResetMode::Hard).unwrap();
+        fs::write("b.txt", "local").unwrap();
+        git_reset("HEAD~1", ResetMode::Keep).unwrap();
+        assert_eq!(head(), second);
+        assert_eq!(read("a.txt"), "a1");
+        assert_eq!(read("b.txt"), "local");
+
+        // --merge: add 한 변경은 되돌리고 add 하지 않은 변경은 남긴다
+        git_reset(&third, ResetMode::Hard).unwrap();
+        fs::write("a.txt", "staged").unwrap();
+        commands::git_add("a.txt").unwrap();
+        fs::write("b.txt", "unstaged").unwrap();
+        git_reset("HEAD", ResetMode::Merge).unwrap();
+        assert_eq!(read("a.txt"), "a2");
+        assert_eq!(read("b.txt"), "unstaged");
+
+        // 경로 reset 은 인덱스만, HEAD 와 작업 트리는 그대로
+        commands::git_add("b.txt").unwrap();
+        git_reset_paths("HEAD~2", &["*.txt"]).unwrap();
+        assert_eq!(head(), third);
+        assert_eq!(read("b.txt"), "unstaged");
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        let a = index.get_path(Path::new("a.txt"), 0).unwrap();
+        assert_eq!(repo.find_blob(a.id).unwrap().content(), b"a1");
+        // HEAD~2 에 없는 b.txt 는 인덱스에서 빠진다
+        assert!(index.get_path(Path::new("b.txt"), 0).is_none());
+        assert!(git_reset_paths("HEAD", &["nope.txt"]).is_err());
+        get_repo();
+    }
 }
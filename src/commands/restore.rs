@@ -1,18 +1,104 @@
-use git2::{build::CheckoutBuilder, Error, Repository};
+use git2::{Error, Index, Pathspec, PathspecFlags, Repository, Tree, build::CheckoutBuilder};
+
+#[derive(Debug, Default, Clone)]
+pub struct RestoreOptions {
+    // --staged: 인덱스를 되돌린다 (unstage)
+    pub staged: bool,
+    // --worktree: 작업 트리를 되돌린다. --staged 가 없으면 기본
+    pub worktree: bool,
+    // --source: 가져올 커밋. 없으면 작업 트리는 인덱스에서, 인덱스는 HEAD 에서 가져온다
+    pub source: Option<String>,
+}
 
 pub fn git_restore(path: &str) -> Result<(), Error> {
+    git_restore_with(&[path], &RestoreOptions::default())
+}
+
+// paths 는 경로나 "*.txt" 같은 glob
+pub fn git_restore_with(paths: &[&str], opts: &RestoreOptions) -> Result<(), Error> {
+    if paths.is_empty() {
+        return Err(Error::from_str("복원할 경로가 없음"));
+    }
     let repo = Repository::open(".")?;
-    let head = repo.head()?;
-    let commit = head.peel_to_commit()?;
-    let binding = commit.tree()?;
-    let tree = binding.as_object();
-    let mut checkout_opts = CheckoutBuilder::new();
-    checkout_opts.path(path);
-    checkout_opts.force(); // --force
-    repo.checkout_tree(&tree, Some(&mut checkout_opts))?;
+    let worktree = opts.worktree || !opts.staged;
+
+    // git 처럼 --source 가 없으면 인덱스는 HEAD 에서, 작업 트리만 복원할 때는 인덱스에서 가져온다
+    let source_rev = match &opts.source {
+        Some(source) => Some(source.as_str()),
+        None if opts.staged => Some("HEAD"),
+        None => None,
+    };
+    // 커밋이 없는 저장소에서 --staged 는 인덱스에서 빼기만 한다
+    let source = match source_rev {
+        Some("HEAD") if opts.source.is_none() && repo.head().is_err() => None,
+        Some(rev) => Some(repo.revparse_single(rev)?.peel_to_commit()?),
+        None => None,
+    };
+    let tree = source.as_ref().map(|commit| commit.tree()).transpose()?;
+
+    let mut index = repo.index()?;
+    for path in paths {
+        let known = match &tree {
+            Some(tree) => matches_tree(tree, path)?,
+            None => false,
+        };
+        // 인덱스를 되돌리거나 인덱스에서 가져올 때는 인덱스에만 있는 경로도 된다
+        let known =
+            known || ((opts.staged || source_rev.is_none()) && matches_index(&index, path)?);
+        if !known {
+            return Err(Error::from_str(&format!(
+                "'{}' 경로가 {} 에 없음",
+                path,
+                source_rev.unwrap_or("인덱스")
+            )));
+        }
+    }
+
+    if opts.staged {
+        // 지정한 경로의 인덱스 항목을 source 트리와 같게 만든다 (없는 항목은 인덱스에서 빠진다)
+        repo.reset_default(
+            source.as_ref().map(|commit| commit.as_object()),
+            paths.iter(),
+        )?;
+        index.read(true)?;
+    }
+
+    if worktree {
+        let mut checkout_opts = CheckoutBuilder::new();
+        for path in paths {
+            checkout_opts.path(path);
+        }
+        checkout_opts.force(); // --force
+        match &tree {
+            Some(tree) => {
+                // --staged 가 없으면 인덱스는 그대로 두고 작업 트리만 바꾼다
+                checkout_opts.update_index(opts.staged);
+                repo.checkout_tree(tree.as_object(), Some(&mut checkout_opts))?;
+            }
+            None => repo.checkout_index(Some(&mut index), Some(&mut checkout_opts))?,
+        }
+    }
     Ok(())
 }
 
+pub(crate) fn matches_tree(tree: &Tree, path: &str) -> Result<bool, Error> {
+    let spec = Pathspec::new([path])?;
+    Ok(spec
+        .match_tree(tree, PathspecFlags::DEFAULT)?
+        .entries()
+        .len()
+        > 0)
+}
+
+pub(crate) fn matches_index(index: &Index, path: &str) -> Result<bool, Error> {
+    let spec = Pathspec::new([path])?;
+    Ok(spec
+        .match_index(index, PathspecFlags::DEFAULT)?
+        .entries()
+        .len()
+        > 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,6 +106,7 @@ mod tests {
     use crate::test_helpers::{get_repo, write_dummy_add_commit};
     use serial_test::serial;
     use std::fs;
+    use std::path::Path;
 
     #[test]
     #[serial]
@@ -51,4 +138,97 @@ mod tests {
             "복원된 파일 내용이 일치하지 않음"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_git_restore_staged_and_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let staged = |path: &str| {
+            let mut index = repo.index().unwrap();
+            index.read(true).unwrap();
+            let id = index.get_path(Path::new(path), 0).unwrap().id;
+            String::from_utf8(repo.find_blob(id).unwrap().content().to_vec()).unwrap()
+        };
+        let read = |path: &str| fs::read_to_string(path).unwrap();
+
+        fs::write("a.txt", "v1").unwrap();
+        commands::git_add("a.txt").unwrap();
+        commands::git_commit("v1").unwrap();
+        fs::write("a.txt", "v2").unwrap();
+        fs::write("b.txt", "b").unwrap();
+        fs::write("c.md", "c").unwrap();
+        for path in ["a.txt", "b.txt", "c.md"] {
+            commands::git_add(path).unwrap();
+        }
+        commands::git_commit("v2").unwrap();
+
+        fs::write("a.txt", "v3").unwrap();
+        commands::git_add("a.txt").unwrap();
+        fs::write("a.txt", "v4").unwrap();
+
+        // 기본은 인덱스에서 작업 트리로
+        git_restore("a.txt").unwrap();
+        assert_eq!(read("a.txt"), "v3");
+
+        // --staged 는 작업 트리를 두고 인덱스만 HEAD 로
+        let opts = RestoreOptions {
+            staged: true,
+            ..Default::default()
+        };
+        git_restore_with(&["a.txt"], &opts).unwrap();
+        assert_eq!(staged("a.txt"), "v2");
+        assert_eq!(read("a.txt"), "v3");
+
+        // --source 로 다른 커밋에서 인덱스와 작업 트리 모두
+        let opts = RestoreOptions {
+            staged: true,
+            worktree: true,
+            source: Some("HEAD~1".to_string()),
+        };
+        git_restore_with(&["a.txt"], &opts).unwrap();
+        assert_eq!(staged("a.txt"), "v1");
+        assert_eq!(read("a.txt"), "v1");
+
+        // 여러 경로와 glob
+        fs::write("b.txt", "changed").unwrap();
+        fs::write("c.md", "changed").unwrap();
+        let opts = RestoreOptions {
+            source: Some("HEAD".to_string()),
+            ..Default::default()
+        };
+        git_restore_with(&["*.txt"], &opts).unwrap();
+        assert_eq!(read("a.txt"), "v2");
+        assert_eq!(read("b.txt"), "b");
+        assert_eq!(read("c.md"), "changed");
+        // --source 만 주면 인덱스는 그대로
+        assert_eq!(staged("a.txt"), "v1");
+
+        // 새로 추가한 파일의 --staged 는 인덱스에서 빼기만 한다
+        fs::write("new.txt", "new").unwrap();
+        commands::git_add("new.txt").unwrap();
+        let opts = RestoreOptions {
+            staged: true,
+            ..Default::default()
+        };
+        git_restore_with(&["new.txt", "c.md"], &opts).unwrap();
+        assert!(
+            repo.index()
+                .unwrap()
+                .get_path(Path::new("new.txt"), 0)
+                .is_none()
+        );
+        assert_eq!(read("new.txt"), "new");
+
+        // 없는 경로는 에러
+        let err = git_restore("nope.txt").unwrap_err();
+        assert!(err.message().contains("'nope.txt'"));
+        let opts = RestoreOptions {
+            source: Some("HEAD~1".to_string()),
+            ..Default::default()
+        };
+        assert!(git_restore_with(&["b.txt"], &opts).is_err());
+        get_repo();
+    }
 }
@@ -1,58 +1,371 @@
-use git2::build::CheckoutBuilder;
-use git2::{ApplyLocation, Oid, Repository};
+use crate::commands::commit::{create_commit, update_head};
+use crate::commands::reset;
+use crate::identity::{self, IdentityOverride};
+use crate::message;
+use crate::sequencer::Sequencer;
+use crate::signing;
+use crate::state;
+use git2::{Commit, Oid, Repository, RepositoryState, RevparseMode, Sort};
+use std::collections::HashSet;
+use std::fs;
+
+// 충돌로 멈췄을 때 안내
+const CONTINUE_HINT: &str = "revert --continue (건너뛰려면 --skip, 취소하려면 --abort)";
+
+#[derive(Debug, Default, Clone)]
+pub struct RevertOptions {
+    // --trailer "Key: value"
+    pub trailers: Vec<(String, String)>,
+    // -s, --signoff
+    pub signoff: bool,
+    // -S / --no-gpg-sign
+    pub sign: Option<bool>,
+    // -m <parent>: 머지 커밋을 되돌릴 때 기준이 되는 부모 (1부터)
+    pub mainline: Option<usize>,
+    // -n, --no-commit: 되돌린 내용을 인덱스와 작업 트리에만 반영한다
+    pub no_commit: bool,
+}
 
 pub fn git_revert(commit_id: &str) -> Result<(), git2::Error> {
+    git_revert_with(commit_id, &RevertOptions::default())
+}
+
+pub fn git_revert_with(commit_id: &str, opts: &RevertOptions) -> Result<(), git2::Error> {
+    git_revert_many(&[commit_id], opts)
+}
+
+// 3-way 로 되돌린다. 충돌이 나면 충돌 표시를 남기고 REVERT_HEAD 를 기록한 채 멈춘다.
+// revs 는 HEAD~1, 짧은 해시, 브랜치 이름 같은 리비전이나 A..B 범위이고, 최신 커밋부터 되돌린다
+pub fn git_revert_many(revs: &[&str], opts: &RevertOptions) -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+
+    let todo = resolve_commits(&repo, revs)?;
+    if todo.is_empty() {
+        return Err(git2::Error::from_str("되돌릴 커밋이 없음"));
+    }
+    // 되돌린 내용이 쌓인 인덱스에 다시 3-way 머지할 수 없으므로 -n 은 커밋 하나만
+    if todo.len() > 1 && opts.no_commit {
+        return Err(git2::Error::from_str(
+            "--no-commit 은 커밋 하나만 되돌릴 때 쓸 수 있음",
+        ));
+    }
+    // 중간에 멈추지 않도록 -m 이 맞는지 먼저 확인한다
+    for oid in &todo {
+        revert_parent(&repo.find_commit(*oid)?, opts.mainline)?;
+    }
+
+    // --abort 로 돌아갈 위치
+    let head_oid = repo.refname_to_id("HEAD")?;
+    repo.reference("ORIG_HEAD", head_oid, true, "revert: updating ORIG_HEAD")?;
+
+    // --no-commit 은 커밋하지 않으니 --continue 할 일이 없다
+    if opts.no_commit {
+        return revert_one(&repo, &repo.find_commit(todo[0])?, opts);
+    }
+    // 커밋 하나여도 옵션(-S 등)을 남겨 두어야 --continue 에서 같은 옵션으로 커밋한다
+    let sequencer = Sequencer {
+        head: head_oid,
+        todo,
+        opts: opts.clone(),
+    };
+    run_sequence(&repo, sequencer)
+}
+
+// 충돌을 해결하고 add 한 뒤 이어서 커밋하고, 남은 커밋이 있으면 계속 되돌린다
+pub fn git_revert_continue() -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    ensure_reverting(&repo)?;
+
+    // REVERT_HEAD 가 없으면 되돌리다 실패한 커밋이므로 다시 되돌린다
+    let picked = repo.state() != RepositoryState::Clean;
+    if picked {
+        ensure_resolved(&repo, CONTINUE_HINT)?;
+        let msg = repo.message()?;
+        // -S / --no-gpg-sign 은 시작할 때 sequencer 에 남겨 둔 값을 쓴다
+        let sign = if Sequencer::exists(&repo) {
+            Sequencer::load(&repo)?.opts.sign
+        } else {
+            None
+        };
+        commit_revert(&repo, &msg, sign)?;
+    }
+    continue_sequence(&repo, picked)
+}
+
+// 되돌리기 전(여러 커밋이면 시작하기 전)으로 돌아간다
+pub fn git_revert_abort() -> Result<(), git2::Error> {
+    let repo = Repository::open(".")?;
+    ensure_reverting(&repo)?;
+
+    let orig_head = if Sequencer::exists(&repo) {
+        repo.find_commit(Sequencer::load(&repo)?.head)?
+    } else {
+        repo.revparse_single("ORIG_HEAD")?.peel_to_commit()?
+    };
+    // add 하지 않은 로컬 변경은 남기고 되돌린다 (reset --merge)
+    reset::reset_merge(&repo, &orig_head)?;
+    repo.cleanup_state()?;
+    Sequencer::remove(&repo)?;
+    println!("revert 를 취소함");
+    Ok(())
+}
+
+// 지금 되돌리던 커밋은 건너뛰고 (변경 사항을 버린다) 남은 커밋을 계속 되돌린다
+pub fn git_revert_skip() -> Result<(), git2::Error> {
     let repo = Repository::open(".")?;
+    ensure_reverting(&repo)?;
 
-    // 타켓 커밋을 Oid로 변환후 찾기
-    let target_oid = Oid::from_str(commit_id)?;
-    let target_commit = repo.find_commit(target_oid)?;
+    // 충돌 외의 이유로 멈췄으면 건너뛸 변경 사항이 없다
+    if repo.find_reference("REVERT_HEAD").is_err() {
+        return Err(git2::Error::from_str(
+            "건너뛸 revert 가 없음 (다시 되돌리려면 revert --continue)",
+        ));
+    }
+    let sequencer = if Sequencer::exists(&repo) {
+        Some(Sequencer::load(&repo)?)
+    } else {
+        None
+    };
+
+    let head = repo.head()?.peel_to_commit()?;
+    reset::reset_merge(&repo, &head)?;
+    finish_pick(&repo)?;
+    // reset 이 sequencer 디렉토리까지 지우므로 다시 기록한다
+    if let Some(sequencer) = sequencer {
+        sequencer.save(&repo)?;
+    }
+    println!("revert 를 건너뜀");
+    continue_sequence(&repo, true)
+}
+
+// 리비전과 범위를 커밋 목록으로. 자식이 부모보다 먼저 오도록 최신 커밋부터
+fn resolve_commits(repo: &Repository, revs: &[&str]) -> Result<Vec<Oid>, git2::Error> {
+    let mut selected = HashSet::new();
+    for rev in revs {
+        if !rev.contains("..") {
+            selected.insert(repo.revparse_single(rev)?.peel_to_commit()?.id());
+            continue;
+        }
+        let spec = repo.revparse(rev)?;
+        if spec.mode().contains(RevparseMode::MERGE_BASE) {
+            return Err(git2::Error::from_str(&format!(
+                "revert 에는 A...B 대신 A..B 범위를 쓰세요: {}",
+                rev
+            )));
+        }
+        let (Some(from), Some(to)) = (spec.from(), spec.to()) else {
+            return Err(git2::Error::from_str(&format!("잘못된 범위: {}", rev)));
+        };
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(to.peel_to_commit()?.id())?;
+        revwalk.hide(from.peel_to_commit()?.id())?;
+        for oid in revwalk {
+            selected.insert(oid?);
+        }
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    for oid in &selected {
+        revwalk.push(*oid)?;
+    }
+    let mut ordered = Vec::new();
+    for oid in revwalk {
+        let oid = oid?;
+        if selected.contains(&oid) {
+            ordered.push(oid);
+        }
+    }
+    Ok(ordered)
+}
+
+// todo 의 커밋을 차례로 되돌린다. 되돌리는 중인 커밋은 끝날 때까지 todo 맨 앞에 남겨 두므로
+// 충돌이나 다른 이유로 멈춰도 --continue 에서 이어서 (커밋하지 못했으면 다시) 되돌린다
+fn run_sequence(repo: &Repository, mut sequencer: Sequencer) -> Result<(), git2::Error> {
+    while let Some(&oid) = sequencer.todo.first() {
+        sequencer.save(repo)?;
+        revert_one(repo, &repo.find_commit(oid)?, &sequencer.opts)?;
+        sequencer.todo.remove(0);
+    }
+    Sequencer::remove(repo)
+}
+
+// done: todo 맨 앞의 커밋을 (커밋하거나 건너뛰어) 끝냈는지
+fn continue_sequence(repo: &Repository, done: bool) -> Result<(), git2::Error> {
+    if !Sequencer::exists(repo) {
+        return Ok(());
+    }
+    let mut sequencer = Sequencer::load(repo)?;
+    if done && !sequencer.todo.is_empty() {
+        sequencer.todo.remove(0);
+    }
+    run_sequence(repo, sequencer)
+}
+
+// 커밋 하나를 되돌릴 때의 기준 부모. 머지 커밋은 -m 으로 어느 쪽을 기준으로 할지 정해야 한다
+fn revert_parent<'r>(
+    target_commit: &Commit<'r>,
+    mainline: Option<usize>,
+) -> Result<Option<Commit<'r>>, git2::Error> {
+    match (target_commit.parent_count(), mainline) {
+        (0, None) => Ok(None),
+        (0 | 1, Some(_)) => Err(git2::Error::from_str(&format!(
+            "커밋 {} 은 머지 커밋이 아닌데 -m 옵션이 주어짐",
+            target_commit.id()
+        ))),
+        (1, None) => Ok(Some(target_commit.parent(0)?)),
+        (_, None) => Err(git2::Error::from_str(&format!(
+            "커밋 {} 은 머지 커밋인데 -m 옵션이 없음",
+            target_commit.id()
+        ))),
+        (count, Some(mainline)) if mainline == 0 || mainline > count => {
+            Err(git2::Error::from_str(&format!(
+                "커밋 {} 에는 부모 {} 이 없음 (1..={})",
+                target_commit.id(),
+                mainline,
+                count
+            )))
+        }
+        (_, Some(mainline)) => Ok(Some(target_commit.parent(mainline - 1)?)),
+    }
+}
+
+fn revert_one(
+    repo: &Repository,
+    target_commit: &Commit,
+    opts: &RevertOptions,
+) -> Result<(), git2::Error> {
+    let parent_commit = revert_parent(target_commit, opts.mainline)?;
 
-    // 타켓 커밋의 첫번째 부모를 찾기
-    let parent_commit = target_commit.parent(0)?;
+    // 커밋 메시지에 Revert 추가
+    let summary = target_commit.summary().unwrap_or("");
+    let mut commit_msg = match &parent_commit {
+        Some(parent) if target_commit.parent_count() > 1 => format!(
+            "Revert \"{}\"\n\nThis reverts commit {}, reversing\nchanges made to {}.\n",
+            summary,
+            target_commit.id(),
+            parent.id()
+        ),
+        _ => format!(
+            "Revert \"{}\"\n\nThis reverts commit {}.\n",
+            summary,
+            target_commit.id()
+        ),
+    };
+    let mut trailers = opts.trailers.clone();
+    if opts.signoff {
+        trailers.push(message::signoff_trailer(repo)?);
+    }
+    if !trailers.is_empty() {
+        commit_msg = message::append_trailers(&commit_msg, &trailers);
+    }
 
-    // 타켓과 부모의 트리 객체 가져오기
-    let target_tree = target_commit.tree()?;
-    let parent_tree = parent_commit.tree()?;
+    // 타겟 커밋과 기준 부모의 차이를 HEAD 에 거꾸로 3-way 머지한다 (루트 커밋은 빈 트리가 부모).
+    // 인덱스와 작업 트리에 반영되고, REVERT_HEAD 와 MERGE_MSG 가 기록된다
+    let mut revert_opts = git2::RevertOptions::new();
+    if let Some(mainline) = opts.mainline {
+        revert_opts.mainline(mainline as u32);
+    }
+    repo.revert(target_commit, Some(&mut revert_opts))?;
 
-    // 부모와 타겟의 순서를 바꿔 역방향 diff를 만들면 타켓 커밋의 변경사항을 되돌리는 패치가 생성
-    let diff = repo.diff_tree_to_tree(Some(&target_tree), Some(&parent_tree), None)?;
+    // --continue 에서 쓸 메시지
+    fs::write(repo.path().join("MERGE_MSG"), &commit_msg)
+        .map_err(|e| git2::Error::from_str(&format!("MERGE_MSG 작성 실패: {}", e)))?;
 
-    repo.apply(&diff, ApplyLocation::Index, None)?;
+    // --no-commit 은 --continue 할 일이 없으므로 충돌이 나도 진행 중인 revert 로 남기지 않는다
+    if opts.no_commit {
+        repo.cleanup_state()?;
+        ensure_resolved(repo, "commit")?;
+        println!(
+            "커밋 {} 을 되돌린 내용을 인덱스에 반영함 (커밋하지 않음)",
+            &target_commit.id().to_string()[..7]
+        );
+        return Ok(());
+    }
+    ensure_resolved(repo, CONTINUE_HINT)?;
+    commit_revert(repo, &commit_msg, opts.sign)
+}
 
+fn commit_revert(
+    repo: &Repository,
+    commit_msg: &str,
+    sign: Option<bool>,
+) -> Result<(), git2::Error> {
     let mut idx = repo.index()?;
     let tree_id = idx.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
 
-    // 작업 디렉토리 업데이트
-    let mut checkout = CheckoutBuilder::new();
-    repo.checkout_index(Some(&mut idx), Some(&mut checkout))?;
-
     let head_oid = repo.refname_to_id("HEAD")?;
     let head_commit = repo.find_commit(head_oid)?;
 
-    let sig = repo.signature()?;
-
-    // 커밋 메시지에 Revert 추가
-    let summary = target_commit.summary().unwrap_or("");
-    let commit_msg = format!("Revert \"{}\"", summary);
+    let author = identity::author_signature(repo, &IdentityOverride::default())?;
+    let committer = identity::committer_signature(repo)?;
 
-    repo.commit(
-        Some("HEAD"),
-        &sig,
-        &sig,
-        &commit_msg,
+    let sign = signing::should_sign(repo, sign, "commit.gpgSign");
+    let oid = create_commit(
+        repo,
+        &author,
+        &committer,
+        commit_msg,
         &tree,
         &[&head_commit],
+        sign,
     )?;
+    let subject = commit_msg.lines().next().unwrap_or("");
+    update_head(repo, oid, &format!("revert: {}", subject))?;
+    finish_pick(repo)?;
 
-    println!("Revert commit created: {}", commit_msg);
+    println!("Revert commit created: {}", subject);
+
+    Ok(())
+}
 
+// 커밋 하나를 끝냈을 때 REVERT_HEAD 와 MERGE_MSG 만 지운다.
+// (cleanup_state 는 sequencer 디렉토리까지 지운다)
+fn finish_pick(repo: &Repository) -> Result<(), git2::Error> {
+    for name in ["REVERT_HEAD", "MERGE_MSG"] {
+        match fs::remove_file(repo.path().join(name)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(git2::Error::from_str(&format!("{} 삭제 실패: {}", name, e)));
+            }
+            _ => {}
+        }
+    }
     Ok(())
 }
 
+fn ensure_reverting(repo: &Repository) -> Result<(), git2::Error> {
+    match repo.state() {
+        RepositoryState::Revert | RepositoryState::RevertSequence => Ok(()),
+        _ if Sequencer::exists(repo) => Ok(()),
+        _ => Err(git2::Error::from_str("진행 중인 revert 가 없음")),
+    }
+}
+
+// 충돌이 남아 있으면 경로들과 함께 멈춘다. next 는 해결한 뒤 할 일
+fn ensure_resolved(repo: &Repository, next: &str) -> Result<(), git2::Error> {
+    let index = repo.index()?;
+    if !index.has_conflicts() {
+        return Ok(());
+    }
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+            paths.push(format!("\t{}", String::from_utf8_lossy(&entry.path)));
+        }
+    }
+    Err(git2::Error::from_str(&format!(
+        "충돌이 남아 있음:\n{}\n충돌을 해결하고 add 한 뒤 {}",
+        paths.join("\n"),
+        next
+    )))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::commands;
     use crate::test_helpers::get_repo;
     use serial_test::serial;
@@ -61,12 +374,12 @@ mod tests {
     #[test]
     #[serial]
     fn test_git_revert() {
-        let _repo = get_repo();
+        let repo = get_repo();
 
         let file_name = "revert.txt";
 
         // 파일 작성
-        fs::write(&file_name, "비빔밥").expect("failed to write file");
+        fs::write(file_name, "비빔밥").expect("failed to write file");
         commands::git_add(file_name).expect("failed to add file");
         let commit_msg = "비빔밥 먹고싶다.";
         commands::git_commit(commit_msg).expect("failed to commit message");
@@ -84,9 +397,309 @@ mod tests {
         assert_eq!(content, "국밥", "파일 변경 안됨");
 
         // git revert
-        commands::git_revert(&head_commit).expect("failed to revert");
+        let opts = RevertOptions {
+            trailers: vec![("Refs".to_string(), "#7".to_string())],
+            ..Default::default()
+        };
+        commands::git_revert_with(&head_commit, &opts).expect("failed to revert");
 
         let content = fs::read_to_string(file_name).expect("failed to read file");
         assert_eq!(content, "비빔밥", "파일 롤백 안됨");
+
+        let revert_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            revert_commit.message().unwrap(),
+            format!(
+                "Revert \"{}\"\n\nThis reverts commit {}.\n\nRefs: #7\n",
+                commit_msg, head_commit
+            )
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_revert_merge_and_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |path: &str, content: &str| {
+            fs::write(path, content).unwrap();
+            commands::git_add(path).unwrap();
+            commands::git_commit(content).unwrap()
+        };
+
+        let root = commit("root.txt", "root");
+        commit("a.txt", "a1");
+        let side = commit("b.txt", "b1");
+        commands::git_reset("HEAD~1", commands::ResetMode::Hard).unwrap();
+        let main = commit("a.txt", "a2");
+
+        // main 과 side 를 합친 머지 커밋
+        fs::write("b.txt", "b1").unwrap();
+        commands::git_add("b.txt").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let sig = repo.signature().unwrap();
+        let parents = [main.as_str(), side.as_str()]
+            .map(|id| repo.revparse_single(id).unwrap().peel_to_commit().unwrap());
+        let merge = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Merge side",
+                &tree,
+                &[&parents[0], &parents[1]],
+            )
+            .unwrap()
+            .to_string();
+
+        // 머지 커밋은 -m 없이 되돌릴 수 없다
+        let err = git_revert(&merge).unwrap_err();
+        assert!(err.message().contains("-m"));
+        let opts = RevertOptions {
+            mainline: Some(3),
+            ..Default::default()
+        };
+        assert!(git_revert_with(&merge, &opts).is_err());
+        let opts = RevertOptions {
+            mainline: Some(1),
+            ..Default::default()
+        };
+        assert!(git_revert_with(&main, &opts).is_err());
+
+        // -m 1: main 쪽을 기준으로 side 에서 들어온 변경을 되돌린다
+        git_revert_with(&merge, &opts).unwrap();
+        assert!(!std::path::Path::new("b.txt").exists());
+        assert_eq!(fs::read_to_string("a.txt").unwrap(), "a2");
+        let revert_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(revert_commit.message().unwrap().contains(&format!(
+            "This reverts commit {}, reversing\nchanges made to {}.",
+            merge, main
+        )));
+
+        // 루트 커밋은 빈 트리를 기준으로 되돌린다
+        git_revert(&root[..7]).unwrap();
+        assert!(!std::path::Path::new("root.txt").exists());
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_revert_conflicts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |path: &str, content: &str| {
+            fs::write(path, content).unwrap();
+            commands::git_add(path).unwrap();
+            commands::git_commit(content).unwrap()
+        };
+        let read = |path: &str| fs::read_to_string(path).unwrap();
+
+        commit("a.txt", "1\n");
+        commit("b.txt", "b\n");
+        let second = commit("a.txt", "2\n");
+        let third = commit("a.txt", "3\n");
+        // 상관없는 파일의 로컬 변경은 --abort, --skip 뒤에도 남는다
+        fs::write("b.txt", "local\n").unwrap();
+
+        // 같은 줄을 나중에 또 바꿨으므로 충돌
+        let err = git_revert(&second[..7]).unwrap_err();
+        assert!(err.message().contains("\ta.txt"));
+        assert_eq!(repo.state(), git2::RepositoryState::RevertSequence);
+        assert!(read("a.txt").contains("<<<<<<<"));
+        assert!(git_revert(&third).is_err());
+        // 충돌을 해결하지 않으면 계속할 수 없다
+        assert!(git_revert_continue().is_err());
+
+        // --abort 는 원래대로
+        git_revert_abort().unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(read("a.txt"), "3\n");
+        assert_eq!(read("b.txt"), "local\n");
+        assert_eq!(repo.head().unwrap().target().unwrap().to_string(), third);
+
+        // --skip 은 이번 커밋을 버린다
+        assert!(git_revert(&second).is_err());
+        git_revert_skip().unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(read("a.txt"), "3\n");
+        assert_eq!(read("b.txt"), "local\n");
+        commands::git_restore("b.txt").unwrap();
+
+        // 해결하고 add 한 뒤 --continue
+        let opts = RevertOptions {
+            signoff: true,
+            ..Default::default()
+        };
+        assert!(git_revert_with(&second, &opts).is_err());
+        fs::write("a.txt", "1\n3\n").unwrap();
+        commands::git_add("a.txt").unwrap();
+        git_revert_continue().unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_id(0).unwrap().to_string(), third);
+        let message = head.message().unwrap();
+        assert!(message.starts_with("Revert \"2\"\n\nThis reverts commit "));
+        assert!(message.contains("Signed-off-by: "));
+
+        // --no-commit 은 인덱스와 작업 트리에만 반영한다
+        let head = repo.head().unwrap().target().unwrap();
+        let opts = RevertOptions {
+            no_commit: true,
+            ..Default::default()
+        };
+        git_revert_with("HEAD", &opts).unwrap();
+        assert_eq!(repo.head().unwrap().target().unwrap(), head);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(read("a.txt"), "3\n");
+        let status = repo.status_file(std::path::Path::new("a.txt")).unwrap();
+        assert_eq!(status, git2::Status::INDEX_MODIFIED);
+
+        // 충돌이 나도 --continue 할 revert 로 남지 않고, 해결한 뒤 직접 커밋한다
+        commands::git_reset("HEAD", commands::ResetMode::Hard).unwrap();
+        let err = git_revert_with(&second, &opts).unwrap_err();
+        assert!(err.message().contains("add 한 뒤 commit"));
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(state::in_progress(&repo).is_none());
+        assert!(read("a.txt").contains("<<<<<<<"));
+        assert!(git_revert_continue().is_err());
+        assert_eq!(repo.head().unwrap().target().unwrap(), head);
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_revert_continue_keeps_sign() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |content: &str| {
+            fs::write("a.txt", content).unwrap();
+            commands::git_add("a.txt").unwrap();
+            commands::git_commit(content).unwrap()
+        };
+
+        commit("1\n");
+        let second = commit("2\n");
+        commit("3\n");
+
+        // 서명 설정은 켜져 있지만 키가 없어 서명하면 실패한다
+        let mut cfg = repo.config().unwrap();
+        cfg.set_bool("commit.gpgSign", true).unwrap();
+        cfg.set_str("gpg.format", "ssh").unwrap();
+        cfg.set_str("user.signingKey", "no-such-key").unwrap();
+
+        let opts = RevertOptions {
+            sign: Some(false),
+            ..Default::default()
+        };
+        assert!(git_revert_with(&second, &opts).is_err());
+        fs::write("a.txt", "1\n3\n").unwrap();
+        commands::git_add("a.txt").unwrap();
+        // --no-gpg-sign 으로 시작했으니 --continue 도 서명하지 않는다
+        git_revert_continue().unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        assert_eq!(
+            signing::verify_commit(&repo, head).unwrap(),
+            signing::Verification::Unsigned
+        );
+        get_repo();
+    }
+
+    #[test]
+    #[serial]
+    fn test_git_revert_many() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let commit = |path: &str, content: &str| {
+            fs::write(path, content).unwrap();
+            commands::git_add(path).unwrap();
+            commands::git_commit(content).unwrap()
+        };
+        let summary = |rev: &str| {
+            let commit = repo.revparse_single(rev).unwrap().peel_to_commit().unwrap();
+            commit.summary().unwrap().to_string()
+        };
+        let exists = |path: &str| std::path::Path::new(path).exists();
+
+        commit("a.txt", "1\n");
+        let e = commit("e.txt", "e\n");
+        commit("b.txt", "b\n");
+        commit("c.txt", "c\n");
+
+        // 범위는 최신 커밋부터 되돌린다
+        git_revert_many(&["HEAD~2..HEAD"], &RevertOptions::default()).unwrap();
+        let revert_c = repo.revparse_single("HEAD~1").unwrap().id().to_string();
+        assert_eq!(summary("HEAD"), "Revert \"b\"");
+        assert_eq!(summary("HEAD~1"), "Revert \"c\"");
+        assert!(!exists("b.txt") && !exists("c.txt"));
+        assert!(!Sequencer::exists(&repo));
+        assert!(git_revert_many(&["HEAD...HEAD~1"], &RevertOptions::default()).is_err());
+        assert!(git_revert_many(&["HEAD..HEAD"], &RevertOptions::default()).is_err());
+
+        let two = commit("a.txt", "2\n");
+        commit("a.txt", "3\n");
+        let d = commit("d.txt", "d\n");
+        let revs = [e.as_str(), &d[..7], two.as_str()];
+
+        // 가운데 커밋에서 충돌이 나면 남은 커밋을 남기고 멈춘다
+        let err = git_revert_many(&revs, &RevertOptions::default()).unwrap_err();
+        assert!(err.message().contains("\ta.txt"));
+        assert_eq!(repo.state(), git2::RepositoryState::RevertSequence);
+        assert!(!exists("d.txt") && exists("e.txt"));
+        assert!(git_revert(&e).is_err());
+
+        // --abort 는 시작하기 전으로
+        git_revert_abort().unwrap();
+        assert_eq!(repo.head().unwrap().target().unwrap().to_string(), d);
+        assert!(exists("d.txt"));
+        assert!(!Sequencer::exists(&repo));
+
+        // 해결하고 --continue 하면 남은 커밋까지 되돌린다
+        assert!(git_revert_many(&revs, &RevertOptions::default()).is_err());
+        fs::write("a.txt", "1\n3\n").unwrap();
+        commands::git_add("a.txt").unwrap();
+        git_revert_continue().unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(!Sequencer::exists(&repo));
+        assert_eq!(summary("HEAD"), "Revert \"e\"");
+        assert_eq!(summary("HEAD~1"), "Revert \"2\"");
+        assert_eq!(summary("HEAD~2"), "Revert \"d\"");
+        assert!(!exists("e.txt"));
+
+        // 충돌이 아닌 이유로 멈춘 커밋도 --continue 에서 다시 되돌린다
+        let head = repo.head().unwrap().target().unwrap();
+        commit("f.txt", "f\n");
+        commit("g.txt", "g\n");
+        fs::write("g.txt", "local\n").unwrap();
+        assert!(git_revert_many(&["HEAD~2..HEAD"], &RevertOptions::default()).is_err());
+        assert!(Sequencer::exists(&repo));
+        assert!(commands::git_commit("oops").is_err());
+        commands::git_restore("g.txt").unwrap();
+        git_revert_continue().unwrap();
+        assert_eq!(summary("HEAD"), "Revert \"f\"");
+        assert_eq!(summary("HEAD~1"), "Revert \"g\"");
+        assert!(!exists("f.txt") && !exists("g.txt"));
+        assert!(!Sequencer::exists(&repo));
+        commands::git_reset(&head.to_string(), commands::ResetMode::Hard).unwrap();
+
+        // --skip 은 충돌난 커밋만 버리고 남은 커밋을 계속 되돌린다
+        let head = repo.head().unwrap().target().unwrap();
+        let revs = ["HEAD~1", two.as_str(), revert_c.as_str()];
+        assert!(git_revert_many(&revs, &RevertOptions::default()).is_err());
+        git_revert_skip().unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(!Sequencer::exists(&repo));
+        assert_eq!(summary("HEAD"), "Revert \"Revert \"c\"\"");
+        assert_eq!(summary("HEAD~1"), "Revert \"Revert \"2\"\"");
+        assert!(exists("c.txt"));
+        assert_eq!(repo.revparse_single("HEAD~2").unwrap().id(), head);
+        // 건너뛸 변경 사항이 없으면 --skip 할 수 없다
+        assert!(git_revert_skip().is_err());
+        get_repo();
     }
 }
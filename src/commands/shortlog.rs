@@ -0,0 +1,118 @@
+use crate::commands::log::{self, LogOptions};
+use crate::pretty;
+use git2::{Error, Repository};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Clone)]
+pub struct ShortlogOptions {
+    // -s: 커밋 제목 없이 개수만
+    pub summary: bool,
+    // -e: 이름 뒤에 이메일도 출력 (이메일까지 같아야 같은 사람)
+    pub email: bool,
+    // -n: 커밋 수가 많은 순서로 (기본은 이름순)
+    pub numbered: bool,
+    // 범위와 필터는 log 와 같다
+    pub log: LogOptions,
+}
+
+// 작성자별로 커밋 제목을 묶는다. .mailmap 으로 같은 사람의 이름/이메일을 합친다
+pub fn git_shortlog(opts: &ShortlogOptions) -> Result<Vec<String>, Error> {
+    let repo = Repository::open(".")?;
+    let mailmap = repo.mailmap()?;
+
+    let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+    for commit in log::walk_commits(&repo, &opts.log)? {
+        let commit = commit?;
+        let author = commit.author_with_mailmap(&mailmap)?;
+        let name = String::from_utf8_lossy(author.name_bytes());
+        let key = if opts.email {
+            format!(
+                "{} <{}>",
+                name,
+                String::from_utf8_lossy(author.email_bytes())
+            )
+        } else {
+            name.to_string()
+        };
+        groups
+            .entry(key)
+            .or_default()
+            .push(pretty::subject(&commit));
+    }
+
+    let mut groups: Vec<(String, Vec<String>)> = groups.into_iter().collect();
+    if opts.numbered {
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+    } else {
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    let mut res = Vec::new();
+    for (author, subjects) in groups {
+        if opts.summary {
+            res.push(format!("{:>6}\t{}", subjects.len(), author));
+            continue;
+        }
+        res.push(format!("{} ({}):", author, subjects.len()));
+        // 오래된 커밋부터
+        res.extend(
+            subjects
+                .iter()
+                .rev()
+                .map(|subject| format!("      {}", subject)),
+        );
+        res.push(String::new());
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{authors_history, get_repo};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_git_shortlog() {
+        let _dir = authors_history();
+
+        assert_eq!(
+            git_shortlog(&ShortlogOptions::default()).unwrap(),
+            [
+                "Kim (2):",
+                "      add a",
+                "      edit a",
+                "",
+                "Lee (1):",
+                "      add b",
+                ""
+            ]
+        );
+
+        let opts = ShortlogOptions {
+            summary: true,
+            email: true,
+            numbered: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            git_shortlog(&opts).unwrap(),
+            [
+                "     2\tKim <kim@example.com>",
+                "     1\tLee <lee@example.com>"
+            ]
+        );
+
+        let opts = ShortlogOptions {
+            summary: true,
+            log: LogOptions {
+                revs: vec!["HEAD~1".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(git_shortlog(&opts).unwrap(), ["     1\tKim", "     1\tLee"]);
+        get_repo();
+    }
+}
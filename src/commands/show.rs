@@ -0,0 +1,111 @@
+use crate::date;
+use crate::signing;
+use git2::{Commit, DiffFormat, Error, ObjectType, Repository};
+
+// 커밋(또는 태그)의 정보와 서명 상태, 변경 내용을 줄 단위로 반환
+pub fn git_show(rev: &str) -> Result<Vec<String>, Error> {
+    let repo = Repository::open(".")?;
+    let object = repo.revparse_single(rev)?;
+    let mut res = Vec::new();
+
+    if object.kind() == Some(ObjectType::Tag) {
+        let tag = object.peel_to_tag()?;
+        res.push(format!("tag {}", tag.name().unwrap_or("")));
+        if let Some(tagger) = tag.tagger() {
+            res.push(format!("Tagger: {}", tagger));
+            res.push(format!("Date:   {}", date::format_default(&tagger.when())));
+        }
+        let verification = signing::verify_tag(&repo, tag.id())?;
+        if verification != signing::Verification::Unsigned {
+            res.push(format!("Signature: {}", verification));
+        }
+        res.push(String::new());
+        // 태그 메시지 뒤에 붙은 서명은 출력하지 않는다
+        let msg = tag.message().unwrap_or("");
+        let msg = &msg[..signing::signature_start(msg.as_bytes()).unwrap_or(msg.len())];
+        res.extend(msg.trim_end().lines().map(str::to_string));
+        res.push(String::new());
+    }
+
+    let commit = object.peel_to_commit()?;
+    show_commit(&repo, &commit, &mut res)?;
+    Ok(res)
+}
+
+fn show_commit(repo: &Repository, commit: &Commit, res: &mut Vec<String>) -> Result<(), Error> {
+    res.push(format!("commit {}", commit.id()));
+    if commit.parent_count() > 1 {
+        let parents: Vec<String> = commit
+            .parent_ids()
+            .map(|id| id.to_string()[..7].to_string())
+            .collect();
+        res.push(format!("Merge: {}", parents.join(" ")));
+    }
+    let verification = signing::verify_commit(repo, commit.id())?;
+    if verification != signing::Verification::Unsigned {
+        res.push(format!("Signature: {}", verification));
+    }
+    let author = commit.author();
+    res.push(format!("Author: {}", author));
+    res.push(format!("Date:   {}", date::format_default(&author.when())));
+    res.push(String::new());
+    let msg = String::from_utf8_lossy(commit.message_bytes());
+    res.extend(msg.trim_end().lines().map(|line| format!("    {}", line)));
+    res.push(String::new());
+
+    // 첫번째 부모(루트 커밋이면 빈 트리)와 비교
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+    res.extend(patch.lines().map(str::to_string));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands;
+    use crate::test_helpers::get_repo;
+    use serial_test::serial;
+    use std::fs;
+
+    #[test]
+    #[serial]
+    fn test_git_show() {
+        let _repo = get_repo();
+
+        fs::write("show.txt", "show me\n").expect("failed to write file");
+        commands::git_add("show.txt").expect("failed to add file");
+        let oid = commands::git_commit("show commit").expect("failed to commit");
+
+        let lines = git_show("HEAD").expect("failed to show");
+        assert_eq!(lines[0], format!("commit {}", oid));
+        assert!(lines.contains(&"    show commit".to_string()));
+        assert!(lines.contains(&"+show me".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("Signature:")));
+
+        // 서명이 아닌 "-----BEGIN " 이 들어간 태그 메시지는 자르지 않는다
+        let opts = commands::TagOptions {
+            message: Some(
+                "see -----BEGIN here\n-----BEGIN PGP SIGNATURE-----\nnot signed".to_string(),
+            ),
+            force: true,
+            ..Default::default()
+        };
+        commands::git_create_tag("v-show", None, &opts).expect("failed to tag");
+        let lines = git_show("v-show").expect("failed to show");
+        assert!(lines.contains(&"see -----BEGIN here".to_string()));
+        assert!(lines.contains(&"not signed".to_string()));
+        commands::git_delete_tag("v-show").expect("failed to delete tag");
+    }
+}
@@ -0,0 +1,154 @@
+use crate::commands::log::{self, LogOptions};
+use crate::date;
+use git2::{Error, Repository};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorStats {
+    // .mailmap 을 적용한 "Name <email>"
+    pub author: String,
+    pub commits: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    // 커밋 수가 많은 순서
+    pub authors: Vec<AuthorStats>,
+    // (그 주 월요일의 1970-01-01 기준 일 수, 커밋 수), 첫 주부터 마지막 주까지 빈 주도 포함
+    pub weeks: Vec<(i64, usize)>,
+}
+
+// 작성자별 커밋 수와 추가/삭제 줄 수, 주별 커밋 수
+pub fn git_stats(opts: &LogOptions) -> Result<Stats, Error> {
+    let repo = Repository::open(".")?;
+    let mailmap = repo.mailmap()?;
+
+    let mut authors: HashMap<String, AuthorStats> = HashMap::new();
+    let mut weeks: BTreeMap<i64, usize> = BTreeMap::new();
+    for commit in log::walk_commits(&repo, opts)? {
+        let commit = commit?;
+        let author = commit.author_with_mailmap(&mailmap)?;
+        let key = format!(
+            "{} <{}>",
+            String::from_utf8_lossy(author.name_bytes()),
+            String::from_utf8_lossy(author.email_bytes())
+        );
+        let stats = authors.entry(key.clone()).or_insert(AuthorStats {
+            author: key,
+            commits: 0,
+            added: 0,
+            removed: 0,
+        });
+        stats.commits += 1;
+
+        // 머지 커밋은 git log --numstat 처럼 줄 수를 세지 않는다
+        if commit.parent_count() <= 1 {
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+            let diff_stats = diff.stats()?;
+            stats.added += diff_stats.insertions();
+            stats.removed += diff_stats.deletions();
+        }
+
+        // 작성자의 로컬 날짜 기준으로 그 주 월요일
+        let when = author.when();
+        let days = (when.seconds() + when.offset_minutes() as i64 * 60).div_euclid(86400);
+        *weeks.entry(days - (days + 3).rem_euclid(7)).or_default() += 1;
+    }
+
+    let mut authors: Vec<AuthorStats> = authors.into_values().collect();
+    authors.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| a.author.cmp(&b.author))
+    });
+
+    let weeks = match (weeks.keys().next(), weeks.keys().next_back()) {
+        (Some(&first), Some(&last)) => (first..=last)
+            .step_by(7)
+            .map(|week| (week, weeks.get(&week).copied().unwrap_or(0)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(Stats { authors, weeks })
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .authors
+            .iter()
+            .map(|stats| stats.author.chars().count())
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "작성자별:")?;
+        for stats in &self.authors {
+            writeln!(
+                f,
+                "  {:<width$}  {:>4} commits  +{} -{}",
+                stats.author,
+                stats.commits,
+                stats.added,
+                stats.removed,
+                width = width
+            )?;
+        }
+
+        write!(f, "주별 커밋 수:")?;
+        for (week, commits) in &self.weeks {
+            let (year, month, day) = date::civil_from_days(*week);
+            write!(
+                f,
+                "\n  {:04}-{:02}-{:02}  {:>4} {}",
+                year,
+                month,
+                day,
+                commits,
+                "#".repeat(*commits)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{authors_history, get_repo};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_git_stats() {
+        let _dir = authors_history();
+
+        let stats = git_stats(&LogOptions::default()).unwrap();
+        assert_eq!(
+            stats.authors,
+            [
+                AuthorStats {
+                    author: "Kim <kim@example.com>".to_string(),
+                    commits: 2,
+                    added: 4,
+                    removed: 1,
+                },
+                AuthorStats {
+                    author: "Lee <lee@example.com>".to_string(),
+                    commits: 1,
+                    added: 1,
+                    removed: 0,
+                },
+            ]
+        );
+        let monday = date::days_from_civil(2024, 2, 26);
+        assert_eq!(stats.weeks, [(monday, 2), (monday + 7, 1)]);
+        assert!(stats.to_string().contains("  2024-03-04     1 #"));
+        get_repo();
+    }
+}
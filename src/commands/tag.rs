@@ -0,0 +1,179 @@
+use crate::date;
+use crate::identity;
+use crate::message::{self, Cleanup};
+use crate::signing::{self, Verification};
+use crate::state;
+use git2::{Error, ObjectType, Oid, Repository};
+use std::fs;
+
+#[derive(Debug, Default, Clone)]
+pub struct TagOptions {
+    // -a: annotated 태그
+    pub annotate: bool,
+    // -m <msg>, 있으면 annotated 태그
+    pub message: Option<String>,
+    // -s / --no-sign, 없으면 tag.gpgSign 설정을 따른다
+    pub sign: Option<bool>,
+    // -f: 같은 이름의 태그를 덮어씀
+    pub force: bool,
+}
+
+pub fn git_show_tag() -> Result<Vec<String>, Error> {
+    let repo = Repository::open(".")?;
+    let names = repo.tag_names(None)?;
+    Ok(names.iter().flatten().map(str::to_string).collect())
+}
+
+pub fn git_create_tag(name: &str, target: Option<&str>, opts: &TagOptions) -> Result<(), Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    let target = repo.revparse_single(target.unwrap_or("HEAD"))?;
+    let sign = signing::should_sign(&repo, opts.sign, "tag.gpgSign");
+
+    // 메시지도 서명도 없으면 lightweight 태그
+    if !opts.annotate && opts.message.is_none() && !sign {
+        repo.tag_lightweight(name, &target, opts.force)?;
+        println!("tag '{}' created", name);
+        return Ok(());
+    }
+
+    let msg = match &opts.message {
+        Some(msg) => message::cleanup_message(msg, Cleanup::Whitespace, '#'),
+        None => edit_tag_message(&repo, name)?,
+    };
+    if msg.trim().is_empty() {
+        return Err(Error::from_str("태그 메시지가 비어 있음"));
+    }
+    // git 처럼 tagger 는 커미터 정보(GIT_COMMITTER_*)를 쓴다
+    let tagger = identity::committer_signature(&repo)?;
+
+    if !sign {
+        repo.tag(name, &target, &tagger, &msg, opts.force)?;
+        println!("tag '{}' created", name);
+        return Ok(());
+    }
+
+    // git2 에는 서명된 태그를 만드는 API 가 없으므로 태그 객체를 직접 작성한다
+    let kind = target
+        .kind()
+        .map(|kind| kind.str())
+        .ok_or_else(|| Error::from_str("알 수 없는 객체 타입"))?;
+    let when = tagger.when();
+    let mut content = format!(
+        "object {}\ntype {}\ntag {}\ntagger {} <{}> {} {}\n\n{}",
+        target.id(),
+        kind,
+        name,
+        tagger.name().unwrap_or(""),
+        tagger.email().unwrap_or(""),
+        when.seconds(),
+        date::format_offset(when.offset_minutes()),
+        msg
+    );
+    content.push_str(&signing::signer(&repo)?.sign(content.as_bytes())?);
+
+    let oid = repo.odb()?.write(ObjectType::Tag, content.as_bytes())?;
+    repo.reference(
+        &format!("refs/tags/{}", name),
+        oid,
+        opts.force,
+        "tag: signed",
+    )?;
+    println!("signed tag '{}' created", name);
+    Ok(())
+}
+
+fn edit_tag_message(repo: &Repository, name: &str) -> Result<String, Error> {
+    let comment = message::comment_char(repo);
+    let path = repo.path().join("TAG_EDITMSG");
+    fs::write(
+        &path,
+        format!(
+            "\n{c}\n{c} Write a message for tag:\n{c}   {}\n{c} Lines starting with '{c}' will be ignored.\n",
+            name,
+            c = comment
+        ),
+    )
+    .map_err(|e| Error::from_str(&format!("TAG_EDITMSG 작성 실패: {}", e)))?;
+    message::launch_editor(repo, &path)?;
+    let edited = fs::read_to_string(&path)
+        .map_err(|e| Error::from_str(&format!("TAG_EDITMSG 읽기 실패: {}", e)))?;
+    Ok(message::cleanup_message(&edited, Cleanup::Strip, comment))
+}
+
+pub fn git_delete_tag(name: &str) -> Result<(), Error> {
+    let repo = Repository::open(".")?;
+    state::ensure_clean(&repo)?;
+    repo.tag_delete(name)?;
+    println!("tag '{}' deleted", name);
+    Ok(())
+}
+
+pub fn git_verify_tag(name: &str) -> Result<Verification, Error> {
+    let repo = Repository::open(".")?;
+    let oid: Oid = repo.refname_to_id(&format!("refs/tags/{}", name))?;
+    if repo.find_tag(oid).is_err() {
+        return Err(Error::from_str(&format!(
+            "'{}' 는 annotated 태그가 아님",
+            name
+        )));
+    }
+    signing::verify_tag(&repo, oid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{get_repo, write_dummy_add_commit};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_git_create_and_delete_tag() {
+        let repo = get_repo();
+        if repo.head().is_err() {
+            write_dummy_add_commit();
+        }
+
+        git_create_tag("v-light", None, &TagOptions::default()).expect("failed to tag");
+        let opts = TagOptions {
+            message: Some("release".to_string()),
+            ..Default::default()
+        };
+        // tagger 는 작성자가 아니라 커미터 정보
+        // SAFETY: serial 테스트라 다른 스레드가 환경 변수를 읽지 않는다
+        unsafe {
+            std::env::set_var("GIT_AUTHOR_NAME", "Author");
+            std::env::set_var("GIT_COMMITTER_NAME", "Committer");
+        }
+        let res = git_create_tag("v-annotated", Some("HEAD"), &opts);
+        unsafe {
+            std::env::remove_var("GIT_AUTHOR_NAME");
+            std::env::remove_var("GIT_COMMITTER_NAME");
+        }
+        res.expect("failed to tag");
+
+        let tags = git_show_tag().unwrap();
+        assert!(tags.contains(&"v-light".to_string()));
+        assert!(tags.contains(&"v-annotated".to_string()));
+
+        let annotated = repo.refname_to_id("refs/tags/v-annotated").unwrap();
+        assert_eq!(
+            repo.find_tag(annotated).unwrap().message(),
+            Some("release\n")
+        );
+        let tag = repo.find_tag(annotated).unwrap();
+        assert_eq!(tag.tagger().unwrap().name(), Some("Committer"));
+        assert_eq!(
+            git_verify_tag("v-annotated").unwrap(),
+            Verification::Unsigned
+        );
+        assert!(git_verify_tag("v-light").is_err());
+
+        git_delete_tag("v-light").expect("failed to delete tag");
+        git_delete_tag("v-annotated").expect("failed to delete tag");
+        let tags = git_show_tag().unwrap();
+        assert!(!tags.contains(&"v-light".to_string()));
+        assert!(!tags.contains(&"v-annotated".to_string()));
+    }
+}
@@ -0,0 +1,369 @@
+use git2::{Error, Signature, Time};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn now() -> Time {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    Time::new(secs, local_offset_minutes())
+}
+
+// libgit2가 계산한 로컬 타임존 오프셋을 빌려 쓴다
+pub fn local_offset_minutes() -> i32 {
+    Signature::now("offset", "offset")
+        .map(|sig| sig.when().offset_minutes())
+        .unwrap_or(0)
+}
+
+// 지원 형식:
+// - "@<unix>" / "<unix> <+hhmm>" (git 내부 형식)
+// - "YYYY-MM-DD", "YYYY-MM-DD HH:MM[:SS]", "YYYY-MM-DDTHH:MM[:SS]" 뒤에 "+hhmm", "+hh:mm", "Z" 오프셋
+// - "now", "yesterday", "<N> <unit> ago", "<N>.<unit>.ago" (상대 날짜)
+pub fn parse_date(input: &str) -> Result<Time, Error> {
+    let input = input.trim();
+    let invalid = || Error::from_str(&format!("날짜 형식을 해석할 수 없음: {}", input));
+
+    if let Some(secs) = parse_relative(input) {
+        let now = now();
+        return Ok(Time::new(now.seconds() - secs, now.offset_minutes()));
+    }
+
+    if let Some(secs) = input.strip_prefix('@') {
+        let secs = secs.parse::<i64>().map_err(|_| invalid())?;
+        return Ok(Time::new(secs, 0));
+    }
+
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    if let [secs, offset] = parts.as_slice()
+        && let Ok(secs) = secs.parse::<i64>()
+    {
+        return Ok(Time::new(secs, parse_offset(offset).ok_or_else(invalid)?));
+    }
+
+    // 날짜와 시간, 오프셋 분리
+    let (date, rest) = match input.find(['T', ' ']) {
+        Some(pos) => (&input[..pos], input[pos + 1..].trim()),
+        None => (input, ""),
+    };
+    let (time, offset) = match rest.find(['+', '-', 'Z', ' ']) {
+        Some(pos) => (rest[..pos].trim(), Some(rest[pos..].trim())),
+        None => (rest, None),
+    };
+
+    let ymd: Vec<i64> = date
+        .split('-')
+        .map(|s| s.parse::<i64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid())?;
+    let [year, month, day] = ymd[..] else {
+        return Err(invalid());
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+
+    let hms: Vec<i64> = if time.is_empty() {
+        Vec::new()
+    } else {
+        time.split(':')
+            .map(|s| s.parse::<i64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?
+    };
+    let (hour, min, sec) = match hms[..] {
+        [] => (0, 0, 0),
+        [h, m] => (h, m, 0),
+        [h, m, s] => (h, m, s),
+        _ => return Err(invalid()),
+    };
+
+    let offset = match offset {
+        Some(offset) => parse_offset(offset).ok_or_else(invalid)?,
+        None => local_offset_minutes(),
+    };
+
+    let secs = days_from_civil(year, month, day) * 86400 + hour * 3600 + min * 60 + sec
+        - offset as i64 * 60;
+    Ok(Time::new(secs, offset))
+}
+
+pub fn format_offset(offset_minutes: i32) -> String {
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let abs = offset_minutes.abs();
+    format!("{}{:02}{:02}", sign, abs / 60, abs % 60)
+}
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// log --date=<format>
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum DateFormat {
+    #[default]
+    Default,
+    Relative,
+    Iso,
+    IsoStrict,
+    Rfc,
+    Short,
+    Raw,
+    Unix,
+    // format:<strftime>
+    Custom(String),
+}
+
+impl DateFormat {
+    pub fn parse(input: &str) -> Result<DateFormat, Error> {
+        if let Some(custom) = input.strip_prefix("format:") {
+            return Ok(DateFormat::Custom(custom.to_string()));
+        }
+        match input {
+            "default" => Ok(DateFormat::Default),
+            "relative" => Ok(DateFormat::Relative),
+            "iso" | "iso8601" => Ok(DateFormat::Iso),
+            "iso-strict" | "iso8601-strict" => Ok(DateFormat::IsoStrict),
+            "rfc" | "rfc2822" => Ok(DateFormat::Rfc),
+            "short" => Ok(DateFormat::Short),
+            "raw" => Ok(DateFormat::Raw),
+            "unix" => Ok(DateFormat::Unix),
+            _ => Err(Error::from_str(&format!(
+                "알 수 없는 날짜 형식: {} (default, relative, iso, iso-strict, rfc, short, raw, unix, format:<...>)",
+                input
+            ))),
+        }
+    }
+}
+
+pub fn format_date(time: &Time, format: &DateFormat) -> String {
+    let offset = format_offset(time.offset_minutes());
+    match format {
+        DateFormat::Default => format_default(time),
+        DateFormat::Relative => format_relative(time),
+        DateFormat::Iso => strftime(time, "%Y-%m-%d %H:%M:%S %z"),
+        DateFormat::IsoStrict => format!(
+            "{}{}:{}",
+            strftime(time, "%Y-%m-%dT%H:%M:%S"),
+            &offset[..3],
+            &offset[3..]
+        ),
+        DateFormat::Rfc => strftime(time, "%a, %e %b %Y %H:%M:%S %z"),
+        DateFormat::Short => strftime(time, "%Y-%m-%d"),
+        DateFormat::Raw => format!("{} {}", time.seconds(), offset),
+        DateFormat::Unix => time.seconds().to_string(),
+        DateFormat::Custom(custom) => strftime(time, custom),
+    }
+}
+
+// git log 기본 날짜 형식: "Fri Mar 1 12:30:00 2024 +0900"
+pub fn format_default(time: &Time) -> String {
+    strftime(time, "%a %b %e %H:%M:%S %Y %z")
+}
+
+// "3 days ago" 처럼 지금과의 차이
+pub fn format_relative(time: &Time) -> String {
+    let diff = now().seconds() - time.seconds();
+    if diff < 0 {
+        return "in the future".to_string();
+    }
+    let (count, unit) = match diff {
+        0..90 => (diff, "second"),
+        90..5400 => ((diff + 30) / 60, "minute"),
+        5400..129600 => ((diff + 1800) / 3600, "hour"),
+        // 14일 미만은 일, 10주 미만은 주, 1년 미만은 달, 그 이상은 년
+        129600..1209600 => ((diff + 43200) / 86400, "day"),
+        1209600..6048000 => ((diff + 302400) / 604800, "week"),
+        6048000..31536000 => ((diff + 1296000) / 2592000, "month"),
+        _ => ((diff + 15768000) / 31536000, "year"),
+    };
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+// 날짜에 쓰는 strftime 의 일부: %Y %y %m %d %e %H %I %M %S %p %a %b %j %z %s %%
+pub fn strftime(time: &Time, format: &str) -> String {
+    let local = time.seconds() + time.offset_minutes() as i64 * 60;
+    let days = local.div_euclid(86400);
+    let secs = local.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    let (hour, min, sec) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+    let mut res = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => res.push_str(&year.to_string()),
+            Some('y') => res.push_str(&format!("{:02}", year.rem_euclid(100))),
+            Some('m') => res.push_str(&format!("{:02}", month)),
+            Some('d') => res.push_str(&format!("{:02}", day)),
+            Some('e') => res.push_str(&day.to_string()),
+            Some('H') => res.push_str(&format!("{:02}", hour)),
+            Some('I') => res.push_str(&format!("{:02}", (hour + 11) % 12 + 1)),
+            Some('M') => res.push_str(&format!("{:02}", min)),
+            Some('S') => res.push_str(&format!("{:02}", sec)),
+            Some('p') => res.push_str(if hour < 12 { "AM" } else { "PM" }),
+            // 1970-01-01 은 목요일
+            Some('a') => res.push_str(WEEKDAYS[(days + 4).rem_euclid(7) as usize]),
+            Some('b') => res.push_str(MONTHS[(month - 1) as usize]),
+            Some('j') => res.push_str(&format!("{:03}", days - days_from_civil(year, 1, 1) + 1)),
+            Some('z') => res.push_str(&format_offset(time.offset_minutes())),
+            Some('s') => res.push_str(&time.seconds().to_string()),
+            Some('%') => res.push('%'),
+            Some(other) => {
+                res.push('%');
+                res.push(other);
+            }
+            None => res.push('%'),
+        }
+    }
+    res
+}
+
+// 지금으로부터 몇 초 전인지 반환
+fn parse_relative(input: &str) -> Option<i64> {
+    let input = input.to_ascii_lowercase().replace('.', " ");
+    match input.as_str() {
+        "now" => return Some(0),
+        "yesterday" => return Some(86400),
+        _ => {}
+    }
+
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    let [count, unit, "ago"] = parts[..] else {
+        return None;
+    };
+    let count: i64 = count.parse().ok()?;
+    let unit = match unit.strip_suffix('s').unwrap_or(unit) {
+        "second" | "sec" => 1,
+        "minute" | "min" => 60,
+        "hour" => 3600,
+        "day" => 86400,
+        "week" => 7 * 86400,
+        "month" => 30 * 86400,
+        "year" => 365 * 86400,
+        _ => return None,
+    };
+    Some(count * unit)
+}
+
+fn parse_offset(offset: &str) -> Option<i32> {
+    if offset == "Z" {
+        return Some(0);
+    }
+    let (sign, digits) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let digits = digits.replace(':', "");
+    if digits.len() != 4 {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let mins: i32 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 60 + mins))
+}
+
+// 1970-01-01 기준 일 수 (proleptic gregorian)
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// days_from_civil 의 역변환
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_formats() {
+        assert_eq!(parse_date("@1700000000").unwrap().seconds(), 1700000000);
+
+        let time = parse_date("1700000000 +0900").unwrap();
+        assert_eq!(time.seconds(), 1700000000);
+        assert_eq!(time.offset_minutes(), 540);
+
+        let time = parse_date("2024-03-01T12:30:00+09:00").unwrap();
+        assert_eq!(time.seconds(), 1709263800);
+        assert_eq!(time.offset_minutes(), 540);
+
+        assert_eq!(parse_date("2024-03-01 Z").unwrap().seconds(), 1709251200);
+        assert!(parse_date("yesterday-ish").is_err());
+    }
+
+    #[test]
+    fn test_parse_relative_date() {
+        let now = now().seconds();
+        let ago = |input: &str| now - parse_date(input).unwrap().seconds();
+        assert!(ago("now") <= 1);
+        assert!((86400..86402).contains(&ago("yesterday")));
+        assert!((2 * 604800..2 * 604800 + 2).contains(&ago("2 weeks ago")));
+        assert!((10800..10802).contains(&ago("3.hours.ago")));
+        assert!((60..62).contains(&ago("1 minute ago")));
+        assert!(parse_date("2 fortnights ago").is_err());
+    }
+
+    #[test]
+    fn test_format_default() {
+        assert_eq!(
+            format_default(&Time::new(1709263800, 540)),
+            "Fri Mar 1 12:30:00 2024 +0900"
+        );
+        assert_eq!(
+            format_default(&Time::new(0, -90)),
+            "Wed Dec 31 22:30:00 1969 -0130"
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        let time = Time::new(1709263800, 540);
+        let format = |input: &str| format_date(&time, &DateFormat::parse(input).unwrap());
+        assert_eq!(format("iso"), "2024-03-01 12:30:00 +0900");
+        assert_eq!(format("iso-strict"), "2024-03-01T12:30:00+09:00");
+        assert_eq!(format("rfc"), "Fri, 1 Mar 2024 12:30:00 +0900");
+        assert_eq!(format("short"), "2024-03-01");
+        assert_eq!(format("raw"), "1709263800 +0900");
+        assert_eq!(format("unix"), "1709263800");
+        assert_eq!(
+            format("format:%y/%m/%d %I%p (%j) 100%%"),
+            "24/03/01 12PM (061) 100%"
+        );
+        assert!(DateFormat::parse("someday").is_err());
+
+        let ago = |secs: i64| format_relative(&Time::new(now().seconds() - secs, 0));
+        assert_eq!(ago(5), "5 seconds ago");
+        assert_eq!(ago(3600), "60 minutes ago");
+        assert_eq!(ago(3 * 86400), "3 days ago");
+        assert_eq!(ago(3 * 604800), "3 weeks ago");
+        assert_eq!(ago(400 * 86400), "1 year ago");
+    }
+}
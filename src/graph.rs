@@ -0,0 +1,163 @@
+use git2::Oid;
+
+// git 과 같은 순서의 lane 색 (red, green, yellow, blue, magenta, cyan)
+const COLORS: [u8; 6] = [31, 32, 33, 34, 35, 36];
+
+// 다음에 올 커밋을 기다리는 세로줄 하나
+struct Lane {
+    oid: Oid,
+    color: usize,
+}
+
+// log --graph 의 lane 상태. 커밋을 토폴로지 순서로 하나씩 넣으면
+// 커밋 줄 앞에 붙일 그래프와 그 아래 이어지는 연결 줄들을 만든다
+pub struct Graph {
+    lanes: Vec<Lane>,
+    next_color: usize,
+    color: bool,
+}
+
+impl Graph {
+    pub fn new(color: bool) -> Graph {
+        Graph {
+            lanes: Vec::new(),
+            next_color: 0,
+            color,
+        }
+    }
+
+    // (커밋 줄 앞부분 "* | ", 커밋 아래 연결 줄들), mark 는 커밋 자리에 찍을 문자 ('*', '<', '>')
+    pub fn next(&mut self, oid: Oid, parents: &[Oid], mark: char) -> (String, Vec<String>) {
+        let col = match self.lanes.iter().position(|lane| lane.oid == oid) {
+            Some(col) => col,
+            // 처음 보는 커밋(브랜치 끝)은 새 lane 에서 시작
+            None => {
+                let color = self.new_color();
+                self.lanes.push(Lane { oid, color });
+                self.lanes.len() - 1
+            }
+        };
+
+        let mut cells = vec![(' ', None); self.lanes.len() * 2];
+        for (i, lane) in self.lanes.iter().enumerate() {
+            cells[i * 2] = if i == col {
+                (mark, None)
+            } else {
+                ('|', Some(lane.color))
+            };
+        }
+        let row = self.render(&cells) + " ";
+
+        // 각 lane 이 다음 상태의 몇 번째 lane 으로 이어지는지 계산한다.
+        // 같은 커밋을 기다리는 lane 은 하나로 합친다
+        let old = std::mem::take(&mut self.lanes);
+        let mut edges: Vec<(usize, usize, usize)> = Vec::new();
+        for (i, lane) in old.iter().enumerate() {
+            if i != col {
+                let j = self.lane_for(lane.oid, lane.color);
+                edges.push((i, j, lane.color));
+                continue;
+            }
+            for (k, &parent) in parents.iter().enumerate() {
+                let color = if k == 0 { lane.color } else { self.new_color() };
+                let j = self.lane_for(parent, color);
+                edges.push((i, j, self.lanes[j].color));
+            }
+        }
+
+        // 한 줄에 한 칸씩 옮기면서 연결 줄을 그린다
+        let width = old.len().max(self.lanes.len()) * 2;
+        let mut pos: Vec<usize> = edges.iter().map(|&(from, _, _)| from).collect();
+        let mut rows = Vec::new();
+        while edges.iter().zip(&pos).any(|(&(_, to, _), &p)| to != p) {
+            let mut cells = vec![(' ', None); width];
+            for (&(_, to, color), p) in edges.iter().zip(pos.iter_mut()) {
+                if to == *p {
+                    cells[*p * 2] = ('|', Some(color));
+                } else if to < *p {
+                    cells[*p * 2 - 1] = ('/', Some(color));
+                    *p -= 1;
+                } else {
+                    cells[*p * 2 + 1] = ('\\', Some(color));
+                    *p += 1;
+                }
+            }
+            rows.push(self.render(&cells));
+        }
+        (row, rows)
+    }
+
+    fn lane_for(&mut self, oid: Oid, color: usize) -> usize {
+        match self.lanes.iter().position(|lane| lane.oid == oid) {
+            Some(j) => j,
+            None => {
+                self.lanes.push(Lane { oid, color });
+                self.lanes.len() - 1
+            }
+        }
+    }
+
+    fn new_color(&mut self) -> usize {
+        self.next_color += 1;
+        (self.next_color - 1) % COLORS.len()
+    }
+
+    fn render(&self, cells: &[(char, Option<usize>)]) -> String {
+        let len = cells
+            .iter()
+            .rposition(|&(c, _)| c != ' ')
+            .map_or(0, |last| last + 1);
+        cells[..len]
+            .iter()
+            .map(|&(c, color)| match color {
+                Some(color) if self.color => format!("\x1b[{}m{}\x1b[m", COLORS[color], c),
+                _ => c.to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oid(n: u8) -> Oid {
+        Oid::from_bytes(&[n; 20]).unwrap()
+    }
+
+    // 커밋 순서대로 그래프만 그려서 줄 단위로 반환
+    fn draw(commits: &[(u8, &[u8])]) -> Vec<String> {
+        let mut graph = Graph::new(false);
+        let mut lines = Vec::new();
+        for &(id, parents) in commits {
+            let parents: Vec<Oid> = parents.iter().map(|&p| oid(p)).collect();
+            let (row, rows) = graph.next(oid(id), &parents, '*');
+            lines.push(format!("{}{}", row, id));
+            lines.extend(rows);
+        }
+        lines
+    }
+
+    #[test]
+    fn test_graph_linear() {
+        assert_eq!(
+            draw(&[(3, &[2]), (2, &[1]), (1, &[])]),
+            ["* 3", "* 2", "* 1"]
+        );
+    }
+
+    #[test]
+    fn test_graph_merge_and_fork() {
+        // 5 = merge(4, 3), 4 -> 2, 3 -> 2, 2 -> 1
+        let lines = draw(&[(5, &[4, 3]), (3, &[2]), (4, &[2]), (2, &[1]), (1, &[])]);
+        assert_eq!(lines, ["* 5", "|\\", "| * 3", "* | 4", "|/", "* 2", "* 1"]);
+    }
+
+    #[test]
+    fn test_graph_color() {
+        let mut graph = Graph::new(true);
+        graph.next(oid(3), &[oid(1), oid(2)], '*');
+        let (row, _) = graph.next(oid(2), &[oid(1)], '>');
+        assert_eq!(row, "\x1b[31m|\x1b[m > ");
+    }
+}
@@ -0,0 +1,126 @@
+use git2::{Error, Repository};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// core.hooksPath 가 있으면 그 경로를, 없으면 .git/hooks 를 사용
+pub fn hooks_dir(repo: &Repository) -> PathBuf {
+    match repo.config().and_then(|cfg| cfg.get_path("core.hooksPath")) {
+        Ok(path) if path.is_absolute() => path,
+        Ok(path) => repo.workdir().unwrap_or(repo.path()).join(path),
+        Err(_) => repo.path().join("hooks"),
+    }
+}
+
+pub fn find_hook(repo: &Repository, name: &str) -> Option<PathBuf> {
+    let path = hooks_dir(repo).join(name);
+    if !path.is_file() {
+        return None;
+    }
+    // git과 마찬가지로 실행 권한이 없는 훅은 무시한다
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = path.metadata().ok()?.permissions().mode();
+        if mode & 0o111 == 0 {
+            println!(
+                "hint: '{}' 훅에 실행 권한이 없어 무시되었습니다",
+                path.display()
+            );
+            return None;
+        }
+    }
+    Some(path)
+}
+
+// 훅이 없으면 Ok, 0이 아닌 코드로 종료되면 Err
+pub fn run_hook(
+    repo: &Repository,
+    name: &str,
+    args: &[&str],
+    stdin: Option<&str>,
+) -> Result<(), Error> {
+    let Some(path) = find_hook(repo, name) else {
+        return Ok(());
+    };
+
+    let mut cmd = Command::new(&path);
+    cmd.args(args)
+        .current_dir(repo.workdir().unwrap_or(repo.path()))
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        });
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| Error::from_str(&format!("{} 훅 실행 실패: {}", name, e)))?;
+    if let Some(input) = stdin
+        && let Some(mut pipe) = child.stdin.take()
+    {
+        // 훅이 stdin을 다 읽지 않고 종료해도 문제 삼지 않는다
+        let _ = pipe.write_all(input.as_bytes());
+    }
+    let status = child
+        .wait()
+        .map_err(|e| Error::from_str(&format!("{} 훅 실행 실패: {}", name, e)))?;
+
+    if !status.success() {
+        return Err(Error::from_str(&format!(
+            "{} 훅이 실패하여 중단합니다 ({})",
+            name, status
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{self, CommitOptions};
+    use crate::test_helpers::{get_repo, write_hook};
+    use serial_test::serial;
+    use std::fs;
+
+    #[test]
+    #[serial]
+    fn test_pre_commit_hook_aborts_commit() {
+        let repo = get_repo();
+        let hook = write_hook(&repo, "pre-commit", "exit 1");
+
+        fs::write("hook.txt", "pre-commit").expect("failed to write file");
+        commands::git_add("hook.txt").expect("failed to add file");
+        assert!(commands::git_commit("blocked by hook").is_err());
+
+        // --no-verify 면 pre-commit 을 건너뛴다
+        let opts = CommitOptions {
+            messages: vec!["skip hook".to_string()],
+            no_verify: true,
+            ..Default::default()
+        };
+        assert!(commands::git_commit_with(&opts).is_ok());
+
+        fs::remove_file(hook).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_commit_msg_hooks_can_edit_message() {
+        let repo = get_repo();
+        let prepare = write_hook(&repo, "prepare-commit-msg", "echo \"source=$2\" >> \"$1\"");
+        let commit_msg = write_hook(&repo, "commit-msg", "sed -i 's/WIP/done/' \"$1\"");
+        let post_commit = write_hook(&repo, "post-commit", "touch .git/post-commit-ran");
+
+        fs::write("hook.txt", "commit-msg").expect("failed to write file");
+        commands::git_add("hook.txt").expect("failed to add file");
+        let oid = commands::git_commit("WIP change").expect("failed to commit");
+
+        let commit = repo.find_commit(oid.parse().unwrap()).unwrap();
+        assert_eq!(commit.message().unwrap(), "done change\nsource=message\n");
+        assert!(repo.path().join("post-commit-ran").exists());
+
+        for hook in [prepare, commit_msg, post_commit] {
+            fs::remove_file(hook).unwrap();
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::date;
+use git2::{Config, Error, Repository, Signature, Time};
+use std::env;
+use std::path::PathBuf;
+
+// --author, --date 로 받은 값
+#[derive(Debug, Default, Clone)]
+pub struct IdentityOverride {
+    pub author: Option<String>,
+    pub date: Option<String>,
+}
+
+pub const MISSING_IDENTITY: &str = "사용자 정보가 설정되지 않음. \
+     'git config user.name \"이름\"', 'git config user.email \"메일\"' 로 설정하거나 \
+     GIT_AUTHOR_NAME / GIT_AUTHOR_EMAIL, GIT_COMMITTER_NAME / GIT_COMMITTER_EMAIL 환경 변수를 지정하세요";
+
+// "Name <email>" 형식 파싱
+pub fn parse_ident(ident: &str) -> Result<(String, String), Error> {
+    let invalid = || Error::from_str(&format!("'{}' 는 'Name <email>' 형식이 아님", ident));
+    let start = ident.find('<').ok_or_else(invalid)?;
+    let end = ident.rfind('>').ok_or_else(invalid)?;
+    if end < start {
+        return Err(invalid());
+    }
+    let name = ident[..start].trim();
+    let email = ident[start + 1..end].trim();
+    if name.is_empty() {
+        return Err(invalid());
+    }
+    Ok((name.to_string(), email.to_string()))
+}
+
+// 우선순위: --author/--date > GIT_AUTHOR_* > user.name/user.email
+pub fn author_signature(
+    repo: &Repository,
+    ov: &IdentityOverride,
+) -> Result<Signature<'static>, Error> {
+    let (name, email) = match &ov.author {
+        Some(author) => parse_ident(author)?,
+        None => lookup(repo, "GIT_AUTHOR_NAME", "GIT_AUTHOR_EMAIL")?,
+    };
+    let when = match &ov.date {
+        Some(d) => date::parse_date(d)?,
+        None => env_date("GIT_AUTHOR_DATE")?,
+    };
+    Signature::new(&name, &email, &when)
+}
+
+// 우선순위: GIT_COMMITTER_* > user.name/user.email
+pub fn committer_signature(repo: &Repository) -> Result<Signature<'static>, Error> {
+    let (name, email) = lookup(repo, "GIT_COMMITTER_NAME", "GIT_COMMITTER_EMAIL")?;
+    let when = env_date("GIT_COMMITTER_DATE")?;
+    Signature::new(&name, &email, &when)
+}
+
+// 커밋에는 작성자와 커미터가 모두 필요하다
+pub fn is_configured(repo: &Repository) -> bool {
+    lookup(repo, "GIT_AUTHOR_NAME", "GIT_AUTHOR_EMAIL").is_ok()
+        && lookup(repo, "GIT_COMMITTER_NAME", "GIT_COMMITTER_EMAIL").is_ok()
+}
+
+// 사용자 정보 저장. global 이면 ~/.gitconfig, 아니면 현재 저장소의 .git/config
+pub fn set_identity(repo: &Repository, name: &str, email: &str, global: bool) -> Result<(), Error> {
+    let mut cfg = if global {
+        let path = match Config::find_global() {
+            Ok(path) => path,
+            Err(_) => env::var("HOME")
+                .map(|home| PathBuf::from(home).join(".gitconfig"))
+                .map_err(|_| Error::from_str("HOME 디렉토리를 찾을 수 없음"))?,
+        };
+        Config::open(&path)?
+    } else {
+        repo.config()?.open_level(git2::ConfigLevel::Local)?
+    };
+    cfg.set_str("user.name", name)?;
+    cfg.set_str("user.email", email)?;
+    Ok(())
+}
+
+fn lookup(repo: &Repository, name_var: &str, email_var: &str) -> Result<(String, String), Error> {
+    let cfg = repo.config()?;
+    let name = env::var(name_var)
+        .ok()
+        .or_else(|| cfg.get_string("user.name").ok())
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| Error::from_str(MISSING_IDENTITY))?;
+    // 이메일은 비어 있어도 git과 마찬가지로 허용
+    let email = env::var(email_var)
+        .ok()
+        .or_else(|| cfg.get_string("user.email").ok())
+        .or_else(|| env::var("EMAIL").ok())
+        .unwrap_or_default();
+    Ok((name, email))
+}
+
+fn env_date(var: &str) -> Result<Time, Error> {
+    match env::var(var) {
+        Ok(d) if !d.is_empty() => date::parse_date(&d),
+        _ => Ok(date::now()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::get_repo;
+    use serial_test::serial;
+
+    #[test]
+    fn test_parse_ident() {
+        let (name, email) = parse_ident("Kim Coduck <coduck@example.com>").unwrap();
+        assert_eq!(name, "Kim Coduck");
+        assert_eq!(email, "coduck@example.com");
+        assert!(parse_ident("no email").is_err());
+        assert!(parse_ident("<only@email>").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_author_signature_override() {
+        let repo = get_repo();
+        let sig = author_signature(
+            &repo,
+            &IdentityOverride {
+                author: Some("Other <other@example.com>".to_string()),
+                date: Some("@1700000000".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(sig.name(), Some("Other"));
+        assert_eq!(sig.email(), Some("other@example.com"));
+        assert_eq!(sig.when().seconds(), 1700000000);
+    }
+
+    #[test]
+    #[serial]
+    fn test_is_configured_needs_committer() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        // 전역 설정의 user.name 을 가린다
+        repo.config().unwrap().set_str("user.name", "").unwrap();
+        assert!(!is_configured(&repo));
+
+        // SAFETY: serial 테스트라 다른 스레드가 환경 변수를 읽지 않는다
+        unsafe {
+            env::set_var("GIT_AUTHOR_NAME", "Author");
+        }
+        assert!(!is_configured(&repo));
+        assert!(committer_signature(&repo).is_err());
+        unsafe {
+            env::set_var("GIT_COMMITTER_NAME", "Committer");
+        }
+        assert!(is_configured(&repo));
+        unsafe {
+            env::remove_var("GIT_AUTHOR_NAME");
+            env::remove_var("GIT_COMMITTER_NAME");
+        }
+    }
+}
@@ -1,3 +1,12 @@
 pub mod commands;
+pub mod date;
+pub mod graph;
+pub mod hooks;
+pub mod identity;
+pub mod message;
+pub mod pretty;
+pub mod sequencer;
+pub mod signing;
+pub mod state;
 #[cfg(test)]
 pub mod test_helpers;
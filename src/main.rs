@@ -1,18 +1,35 @@
-use git_playground::commands;
-use std::io::{self, BufRead, Write};
+use git_playground::commands::{
+    self, BranchListOptions, CheckoutOptions, CommitOptions, ExportFormat, ExportOptions,
+    FastForward, LogOptions, ResetMode, RestoreOptions, RevertOptions, ShortlogOptions, TagOptions,
+};
+use git_playground::date::DateFormat;
+use git_playground::identity;
+use git_playground::message::{self, Cleanup};
+use git_playground::pretty::Format;
+use git_playground::state;
+use git2::Repository;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
 
 pub fn main() -> Result<(), git2::Error> {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
 
     loop {
+        // 머지, revert 등이 진행 중이면 명령마다 알려준다
+        if let Ok(repo) = Repository::open(".")
+            && let Some(notice) = state::in_progress(&repo)
+        {
+            println!("{}", notice);
+        }
         print!("git playground(도움말 help): ");
         stdout.flush().unwrap();
         let mut input = String::new();
         stdin.lock().read_line(&mut input).unwrap();
         let input = input.trim();
 
-        let tokens: Vec<&str> = input.split_whitespace().collect();
+        let args = split_args(input);
+        let tokens: Vec<&str> = args.iter().map(String::as_str).collect();
         if tokens.is_empty() {
             continue;
         }
@@ -35,96 +52,176 @@ pub fn main() -> Result<(), git2::Error> {
                     }
                 }
             }
-            "commit" => {
-                if tokens.len() < 2 {
-                    println!("input commit message");
-                } else {
-                    let commit_msg = tokens[1..].join(" ");
-                    if let Err(e) = commands::git_commit(&commit_msg) {
+            "commit" => match parse_commit_args(&tokens[1..]) {
+                Ok(opts) => {
+                    ensure_identity();
+                    if let Err(e) = commands::git_commit_with(&opts) {
                         println!("commit error: {}", e);
                     }
                 }
-            }
+                Err(e) => println!("{}", e),
+            },
             "push" => {
-                if tokens.len() < 3 {
-                    println!("입력 형식: push <remote> <refspec>");
+                let no_verify = tokens.contains(&"--no-verify");
+                let rest: Vec<&str> = tokens[1..]
+                    .iter()
+                    .copied()
+                    .filter(|t| *t != "--no-verify")
+                    .collect();
+                if rest.len() < 2 {
+                    println!("입력 형식: push [--no-verify] <remote> <refspec>");
                 } else {
-                    let remote = tokens[1];
-                    let refspec = tokens[2];
-                    if let Err(e) = commands::git_push(remote, refspec) {
+                    let remote = rest[0];
+                    let refspec = rest[1];
+                    if let Err(e) = commands::git_push_with(remote, refspec, no_verify) {
                         println!("push error: {}", e);
                     }
                 }
             }
-            "revert" => {
-                if tokens.len() < 2 {
-                    println!("입력 형식: revert <commit_id>");
-                } else {
-                    if let Err(e) = commands::git_revert(tokens[1]) {
+            "revert" => match &tokens[1..] {
+                ["--continue"] => {
+                    ensure_identity();
+                    if let Err(e) = commands::git_revert_continue() {
                         println!("revert error: {}", e);
                     }
                 }
-            }
-            // 왜 log는 vec 반환해서 여기서 출력하는데 이 친구는 그렇게 안함.
-            // 뭐가 더 좋을까?
-            "branch" => {
-                if tokens.len() == 1 {
-                    if let Err(e) = commands::git_show_branch() {
-                        println!("branch show error: {}", e);
-                    }
-                } else if tokens.len() == 2 {
-                    if let Err(e) = commands::git_create_branch(tokens[1]) {
-                        println!("create branch error: {}", e);
+                ["--abort"] => {
+                    if let Err(e) = commands::git_revert_abort() {
+                        println!("revert error: {}", e);
                     }
-                } else if tokens.len() == 3 && tokens[1] == "-d" {
-                    if let Err(e) = commands::git_delete_branch(tokens[2]) {
-                        println!("delete branch error: {}", e);
+                }
+                ["--skip"] => {
+                    if let Err(e) = commands::git_revert_skip() {
+                        println!("revert error: {}", e);
                     }
                 }
-            }
-            "checkout" => {
-                if tokens.len() != 2 {
-                    println!("입력 형식: checkout <name>");
+                args => match parse_revert_args(args) {
+                    Ok((revs, opts)) => {
+                        ensure_identity();
+                        if let Err(e) = commands::git_revert_many(&revs, &opts) {
+                            println!("revert error: {}", e);
+                        }
+                    }
+                    Err(e) => println!("{}", e),
+                },
+            },
+            // 왜 log는 vec 반환해서 여기서 출력하는데 이 친구는 그렇게 안함.
+            // 뭐가 더 좋을까?
+            "branch" => run_branch(&tokens[1..]),
+            "checkout" => run_checkout(&tokens[1..], false),
+            "switch" => run_checkout(&tokens[1..], true),
+            "restore" => match parse_restore_args(&tokens[1..]) {
+                Ok((paths, opts)) => match commands::git_restore_with(&paths, &opts) {
+                    Ok(()) => println!("파일 복원 완료: {}", paths.join(" ")),
+                    Err(e) => println!("restore error: {}", e),
+                },
+                Err(e) => println!("{}", e),
+            },
+            "reset" => run_reset(&tokens[1..]),
+            "merge" => run_merge(&tokens[1..]),
+            "lint" => {
+                if tokens.len() > 2 {
+                    println!("입력 형식: lint [<commit>|<A..B>]");
                 } else {
-                    if let Err(e) = commands::git_checkout(tokens[1]) {
-                        println!("checkout error: {}", e);
-                    } else {
-                        println!("Switched to branch '{}'", tokens[1]);
+                    match commands::git_lint(tokens.get(1).copied()) {
+                        Ok(reports) if reports.is_empty() => println!("lint 위반 사항 없음"),
+                        Ok(reports) => {
+                            for (commit, violations) in reports {
+                                println!("{}", commit);
+                                for v in violations {
+                                    println!("  {}", v);
+                                }
+                            }
+                        }
+                        Err(e) => println!("lint error: {}", e),
                     }
                 }
             }
-            "restore" => {
-                if tokens.len() < 2 {
-                    println!("복원할 파일 경로를 입력해주세요.");
-                } else {
-                    if let Err(e) = commands::git_restore(tokens[1]) {
-                        println!("restore error: {}", e);
-                    } else {
-                        println!("파일 복원 완료: {}", tokens[1]);
+            "tag" => run_tag(&tokens[1..]),
+            "show" => match commands::git_show(tokens.get(1).copied().unwrap_or("HEAD")) {
+                Ok(lines) => {
+                    for line in lines {
+                        println!("{}", line);
                     }
                 }
-            }
-            "reset" => {
-                if tokens.len() != 3 {
-                    println!("입력 형식: reset <파일 경로> <soft|mixed|hard>");
+                Err(e) => println!("show error: {}", e),
+            },
+            "log" => match parse_log_args(&tokens[1..]) {
+                Ok(opts) => {
+                    println!("커밋 로그:");
+                    if let Err(e) = commands::git_log(&opts, |line| println!("{}", line)) {
+                        println!("log error: {}", e);
+                    }
+                }
+                Err(e) => println!("{}", e),
+            },
+            "shortlog" => match parse_shortlog_args(&tokens[1..]) {
+                Ok(opts) => match commands::git_shortlog(&opts) {
+                    Ok(lines) => {
+                        for line in lines {
+                            println!("{}", line);
+                        }
+                    }
+                    Err(e) => println!("shortlog error: {}", e),
+                },
+                Err(e) => println!("{}", e),
+            },
+            "stats" => match parse_log_args(&tokens[1..]) {
+                Ok(opts) => match commands::git_stats(&opts) {
+                    Ok(stats) => println!("{}", stats),
+                    Err(e) => println!("stats error: {}", e),
+                },
+                Err(e) => println!("{}", e),
+            },
+            "export" => match parse_export_args(&tokens[1..]) {
+                Ok((opts, output)) => match commands::git_export(&opts) {
+                    Ok(text) => match output {
+                        Some(path) => match fs::write(path, text + "\n") {
+                            Ok(()) => println!("{} 에 저장함", path),
+                            Err(e) => println!("export error: {}", e),
+                        },
+                        None => println!("{}", text),
+                    },
+                    Err(e) => println!("export error: {}", e),
+                },
+                Err(e) => println!("{}", e),
+            },
+            "reflog" => {
+                // reflog [show] [<ref>]
+                let args: Vec<&str> = tokens[1..]
+                    .iter()
+                    .copied()
+                    .skip_while(|t| *t == "show")
+                    .collect();
+                if args.len() > 1 {
+                    println!("입력 형식: reflog [show] [<ref>]");
                 } else {
-                    let file_path = tokens[1];
-                    let reset_type = tokens[2];
-                    if let Err(e) = commands::git_reset(file_path, reset_type) {
-                        println!("reset error: {}", e);
-                    } else {
-                        println!("reset 완료: {} ({})", file_path, reset_type);
+                    match commands::git_reflog(args.first().copied()) {
+                        Ok(lines) => {
+                            for line in lines {
+                                println!("{}", line);
+                            }
+                        }
+                        Err(e) => println!("reflog error: {}", e),
                     }
                 }
             }
-            "log" => match commands::git_log() {
-                Ok(logs) => {
-                    println!("커밋 로그:");
-                    for msg in logs {
-                        println!("{}", msg);
+            "recover" => match tokens.len() {
+                1 => match commands::git_recover_list() {
+                    Ok(lines) if lines.is_empty() => println!("잃어버린 커밋 없음"),
+                    Ok(lines) => {
+                        for line in lines {
+                            println!("{}", line);
+                        }
+                    }
+                    Err(e) => println!("recover error: {}", e),
+                },
+                3 => {
+                    if let Err(e) = commands::git_recover(tokens[1], tokens[2]) {
+                        println!("recover error: {}", e);
                     }
                 }
-                Err(e) => println!("log error: {}", e),
+                _ => println!("입력 형식: recover [<commit|HEAD@{{n}}> <새 브랜치>]"),
             },
             "q" => break,
             _ => println!("존재하지 않는 명령어임"),
@@ -133,3 +230,597 @@ pub fn main() -> Result<(), git2::Error> {
 
     Ok(())
 }
+
+// 따옴표로 묶인 인자를 하나로 취급하는 간단한 토크나이저
+fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+fn parse_commit_args(args: &[&str]) -> Result<CommitOptions, String> {
+    let mut opts = CommitOptions::default();
+
+    // 옵션 없이 메시지만 입력한 경우 (commit <msg>)
+    if !args.is_empty() && !args[0].starts_with('-') {
+        opts.messages.push(args.join(" "));
+        return Ok(opts);
+    }
+
+    let usage = "입력 형식: commit [-m <msg>]... [-F <file>] [-e] [-n] [--cleanup=<mode>] \
+                 [--author <\"Name <email>\">] [--date <date>] [--trailer <key:value>]... [-s] \
+                 [--amend [--no-edit]] [-S|--no-gpg-sign]";
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "-m" | "--message" => {
+                let msg = iter.next().ok_or(usage)?;
+                opts.messages.push(msg.to_string());
+            }
+            "-F" | "--file" => {
+                let file = iter.next().ok_or(usage)?;
+                // REPL 의 입력도 stdin 이라 EOF 까지 읽으면 세션이 끝나버린다
+                if *file == "-" {
+                    return Err(
+                        "REPL 에서는 -F - 를 쓸 수 없음 (메시지 파일 경로, -m, 에디터를 사용)"
+                            .to_string(),
+                    );
+                }
+                opts.file = Some(file.to_string());
+            }
+            "-e" | "--edit" => opts.edit = true,
+            "-n" | "--no-verify" => opts.no_verify = true,
+            "-s" | "--signoff" => opts.signoff = true,
+            "-S" | "--gpg-sign" => opts.sign = Some(true),
+            "--no-gpg-sign" => opts.sign = Some(false),
+            "--amend" => opts.amend = true,
+            "--no-edit" => opts.no_edit = true,
+            "--trailer" => {
+                let trailer = iter.next().ok_or(usage)?;
+                opts.trailers
+                    .push(message::parse_trailer_arg(trailer).map_err(|e| e.to_string())?);
+            }
+            "--author" => {
+                let author = iter.next().ok_or(usage)?;
+                opts.identity.author = Some(author.to_string());
+            }
+            "--date" => {
+                let date = iter.next().ok_or(usage)?;
+                opts.identity.date = Some(date.to_string());
+            }
+            _ => {
+                if let Some(mode) = arg.strip_prefix("--cleanup=") {
+                    opts.cleanup = Some(Cleanup::parse(mode).map_err(|e| e.to_string())?);
+                } else if let Some(author) = arg.strip_prefix("--author=") {
+                    opts.identity.author = Some(author.to_string());
+                } else if let Some(date) = arg.strip_prefix("--date=") {
+                    opts.identity.date = Some(date.to_string());
+                } else {
+                    return Err(usage.to_string());
+                }
+            }
+        }
+    }
+
+    if opts.file.is_some() && !opts.messages.is_empty() {
+        return Err("-m 과 -F 는 함께 사용할 수 없음".to_string());
+    }
+    Ok(opts)
+}
+
+fn parse_revert_args<'a>(args: &[&'a str]) -> Result<(Vec<&'a str>, RevertOptions), String> {
+    let usage = "입력 형식: revert [-n] [-m <parent>] [--trailer <key:value>]... [-s] [-S] <commit>|<A..B>... (-n 은 커밋 하나만) | revert --continue | --skip | --abort";
+    let mut opts = RevertOptions::default();
+    let mut revs = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "-s" | "--signoff" => opts.signoff = true,
+            "-S" | "--gpg-sign" => opts.sign = Some(true),
+            "--no-gpg-sign" => opts.sign = Some(false),
+            "-n" | "--no-commit" => opts.no_commit = true,
+            "-m" | "--mainline" => {
+                let parent = iter.next().ok_or(usage)?;
+                opts.mainline = Some(
+                    parent
+                        .parse()
+                        .map_err(|_| format!("-m 에는 부모 번호(1부터)를 주세요: {}", parent))?,
+                );
+            }
+            "--trailer" => {
+                let trailer = iter.next().ok_or(usage)?;
+                opts.trailers
+                    .push(message::parse_trailer_arg(trailer).map_err(|e| e.to_string())?);
+            }
+            _ if !arg.starts_with('-') => revs.push(arg),
+            _ => return Err(usage.to_string()),
+        }
+    }
+    if revs.is_empty() {
+        return Err(usage.to_string());
+    }
+    Ok((revs, opts))
+}
+
+// checkout 과 switch. switch 는 -c 로 새 브랜치를 만들고, 커밋으로 이동하려면 --detach 가 필요하다
+fn run_checkout(args: &[&str], switch: bool) {
+    let usage = if switch {
+        "입력 형식: switch [-f|--discard-changes] [-m] <branch>|- | switch -c <new> [<start-point>] | switch --detach <commit>"
+    } else {
+        "입력 형식: checkout [-f] [-m] <name>|- | checkout -b <new> [<start-point>] | checkout --orphan <name>"
+    };
+    let mut opts = CheckoutOptions::default();
+    let mut new_branch = None;
+    let mut orphan = None;
+    let mut detach = false;
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "-f" | "--force" => opts.force = true,
+            "--discard-changes" if switch => opts.force = true,
+            "-m" | "--merge" => opts.merge = true,
+            "--detach" if switch => detach = true,
+            "-b" if !switch => new_branch = iter.next(),
+            "-c" | "--create" if switch => new_branch = iter.next(),
+            "--orphan" => orphan = iter.next(),
+            // "-" 는 직전 브랜치
+            _ if arg == "-" || !arg.starts_with('-') => positional.push(arg),
+            _ => return println!("{}", usage),
+        }
+    }
+
+    // 어디로 이동했는지(브랜치, 새 브랜치, detached HEAD)는 각 명령이 출력한다
+    let res = match (new_branch, orphan, &positional[..]) {
+        (Some(name), None, [] | [_]) => {
+            commands::git_checkout_new_branch(name, positional.first().copied(), &opts)
+        }
+        (None, Some(name), []) => commands::git_checkout_orphan(name),
+        (None, None, [target]) if switch => commands::git_switch(target, detach, &opts),
+        (None, None, [target]) => commands::git_checkout_with(target, &opts),
+        _ => return println!("{}", usage),
+    };
+    match res {
+        Ok(()) => {}
+        Err(e) if switch => println!("switch error: {}", e),
+        Err(e) => println!("checkout error: {}", e),
+    }
+}
+
+// restore [-S|--staged] [-W|--worktree] [-s|--source <rev>] [--] <path>...
+fn parse_restore_args<'a>(args: &[&'a str]) -> Result<(Vec<&'a str>, RestoreOptions), String> {
+    let usage = "입력 형식: restore [--staged] [--worktree] [--source <rev>] [--] <path>...";
+    let mut opts = RestoreOptions::default();
+    let mut paths = Vec::new();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "-S" | "--staged" => opts.staged = true,
+            "-W" | "--worktree" => opts.worktree = true,
+            "-s" | "--source" => match iter.next() {
+                Some(source) => opts.source = Some(source.to_string()),
+                None => return Err(usage.to_string()),
+            },
+            "--" => {
+                paths.extend(iter.by_ref());
+            }
+            _ if arg.starts_with("--source=") => {
+                opts.source = Some(arg["--source=".len()..].to_string())
+            }
+            _ if !arg.starts_with('-') => paths.push(arg),
+            _ => return Err(usage.to_string()),
+        }
+    }
+    if paths.is_empty() {
+        return Err("복원할 파일 경로를 입력해주세요.".to_string());
+    }
+    Ok((paths, opts))
+}
+
+// merge [--ff|--ff-only|--no-ff] <branch> | merge --continue | --abort
+fn run_merge(args: &[&str]) {
+    let usage = "입력 형식: merge [--ff|--ff-only|--no-ff] <branch> | merge --continue | --abort";
+    let res = match args {
+        ["--continue"] => {
+            ensure_identity();
+            Some(commands::git_merge_continue())
+        }
+        ["--abort"] => Some(commands::git_merge_abort()),
+        _ => None,
+    };
+    if let Some(res) = res {
+        if let Err(e) = res {
+            println!("merge error: {}", e);
+        }
+        return;
+    }
+
+    let mut ff = FastForward::default();
+    let mut branch = None;
+    for &arg in args {
+        match arg {
+            "--ff" => ff = FastForward::Allow,
+            "--ff-only" => ff = FastForward::Only,
+            "--no-ff" => ff = FastForward::Never,
+            _ if !arg.starts_with('-') && branch.is_none() => branch = Some(arg),
+            _ => return println!("{}", usage),
+        }
+    }
+    let Some(branch) = branch else {
+        return println!("{}", usage);
+    };
+    ensure_identity();
+    if let Err(e) = commands::git_merge_with(branch, ff) {
+        println!("merge error: {}", e);
+    }
+}
+
+// reset [--soft|--mixed|--hard|--keep|--merge] [<rev>] | reset [<rev>] [--] <path>...
+fn run_reset(args: &[&str]) {
+    let usage = "입력 형식: reset [--soft|--mixed|--hard|--keep|--merge] [<rev>] | reset [<rev>] [--] <path>...";
+    let mut mode = None;
+    let mut positional = Vec::new();
+    let mut paths = Vec::new();
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--" => paths.extend(iter.by_ref().copied()),
+            _ if arg.starts_with('-') => match ResetMode::parse(arg) {
+                Ok(parsed) if mode.is_none() => mode = Some(parsed),
+                Ok(_) => return println!("reset 모드는 하나만 지정할 수 있음"),
+                Err(e) => return println!("reset error: {}\n{}", e, usage),
+            },
+            _ => positional.push(arg),
+        }
+    }
+
+    // "--" 가 없으면 첫 인자가 리비전으로 읽히는지 보고 리비전/경로를 나눈다
+    let is_rev = |rev: &str| {
+        Repository::open(".")
+            .and_then(|repo| repo.revparse_single(rev)?.peel_to_commit().map(|_| ()))
+            .is_ok()
+    };
+    let (rev, mut rest) = match positional.split_first() {
+        Some((first, rest)) if is_rev(first) => (*first, rest.to_vec()),
+        _ => ("HEAD", positional),
+    };
+    rest.extend(paths);
+
+    let res = if rest.is_empty() {
+        commands::git_reset(rev, mode.unwrap_or_default())
+    } else {
+        match mode {
+            None | Some(ResetMode::Mixed) => commands::git_reset_paths(rev, &rest),
+            Some(_) => return println!("경로와 함께는 --mixed 만 쓸 수 있음"),
+        }
+    };
+    if let Err(e) = res {
+        println!("reset error: {}", e);
+    }
+}
+
+fn run_branch(args: &[&str]) {
+    let usage = "입력 형식: branch [-v|-vv] [-r|-a] | branch <name> [<start-point>] | branch -d|-D <name>... | branch -m|-M|-c|-C [<old>] <new> | branch --set-upstream-to=<upstream> [<name>] | branch --unset-upstream [<name>]";
+    match args {
+        _ if args
+            .iter()
+            .all(|arg| matches!(*arg, "-v" | "-vv" | "-r" | "-a" | "--verbose")) =>
+        {
+            let mut opts = BranchListOptions::default();
+            for arg in args {
+                match *arg {
+                    "-v" | "--verbose" => opts.verbose += 1,
+                    "-vv" => opts.verbose += 2,
+                    "-r" => opts.remotes = true,
+                    _ => opts.all = true,
+                }
+            }
+            if let Err(e) = commands::git_show_branch(&opts) {
+                println!("branch show error: {}", e);
+            }
+        }
+        [flag, rest @ ..] if flag.starts_with("--set-upstream-to") || *flag == "-u" => {
+            let (upstream, name) = match (flag.strip_prefix("--set-upstream-to="), rest) {
+                (Some(upstream), []) => (upstream, None),
+                (Some(upstream), [name]) => (upstream, Some(*name)),
+                (None, [upstream]) => (*upstream, None),
+                (None, [upstream, name]) => (*upstream, Some(*name)),
+                _ => return println!("{}", usage),
+            };
+            if let Err(e) = commands::git_set_upstream(name, upstream) {
+                println!("branch error: {}", e);
+            }
+        }
+        ["--unset-upstream", rest @ ..] if rest.len() <= 1 => {
+            if let Err(e) = commands::git_unset_upstream(rest.first().copied()) {
+                println!("branch error: {}", e);
+            }
+        }
+        [name] | [name, _] if !name.starts_with('-') => {
+            if let Err(e) = commands::git_create_branch_at(name, args.get(1).copied()) {
+                println!("create branch error: {}", e);
+            }
+        }
+        [flag @ ("-d" | "-D"), names @ ..] if !names.is_empty() => {
+            for name in names {
+                if let Err(e) = commands::git_delete_branch(name, *flag == "-D") {
+                    println!("delete branch error: {}", e);
+                }
+            }
+        }
+        [flag @ ("-m" | "-M" | "-c" | "-C"), rest @ ..] => {
+            let (old, new) = match rest {
+                [new] => (None, *new),
+                [old, new] => (Some(*old), *new),
+                _ => return println!("{}", usage),
+            };
+            let force = matches!(*flag, "-M" | "-C");
+            let res = if matches!(*flag, "-m" | "-M") {
+                commands::git_rename_branch(old, new, force)
+            } else {
+                commands::git_copy_branch(old, new, force)
+            };
+            if let Err(e) = res {
+                println!("branch error: {}", e);
+            }
+        }
+        _ => println!("{}", usage),
+    }
+}
+
+fn run_tag(args: &[&str]) {
+    let usage = "입력 형식: tag [-a] [-s] [-f] [-m <msg>] <name> [<commit>] | tag -d <name> | tag -v <name>";
+    match args {
+        [] | ["-l"] => match commands::git_show_tag() {
+            Ok(tags) => {
+                for tag in tags {
+                    println!("{}", tag);
+                }
+            }
+            Err(e) => println!("tag error: {}", e),
+        },
+        ["-d", name] => {
+            if let Err(e) = commands::git_delete_tag(name) {
+                println!("delete tag error: {}", e);
+            }
+        }
+        ["-v", name] => match commands::git_verify_tag(name) {
+            Ok(verification) => println!("{}", verification),
+            Err(e) => println!("verify tag error: {}", e),
+        },
+        _ => {
+            let mut opts = TagOptions::default();
+            let mut positional = Vec::new();
+            let mut iter = args.iter();
+            while let Some(&arg) = iter.next() {
+                match arg {
+                    "-a" => opts.annotate = true,
+                    "-s" => opts.sign = Some(true),
+                    "--no-sign" => opts.sign = Some(false),
+                    "-f" => opts.force = true,
+                    "-m" => match iter.next() {
+                        Some(msg) => opts.message = Some(msg.to_string()),
+                        None => return println!("{}", usage),
+                    },
+                    _ if !arg.starts_with('-') => positional.push(arg),
+                    _ => return println!("{}", usage),
+                }
+            }
+            match positional[..] {
+                [name] | [name, _] => {
+                    if let Err(e) =
+                        commands::git_create_tag(name, positional.get(1).copied(), &opts)
+                    {
+                        println!("create tag error: {}", e);
+                    }
+                }
+                _ => println!("{}", usage),
+            }
+        }
+    }
+}
+
+fn parse_log_args(args: &[&str]) -> Result<LogOptions, String> {
+    let usage = "입력 형식: log [--graph] [--topo-order] [--oneline] [--pretty=<format>] [--date=<format>] [--no-color] [-n <N>] [--skip <N>] [--author <re>] [--committer <re>] [--grep <re>] [-i] [--since <date>] [--until <date>] [--trailer <key>[=<value>]] [--show-signature] [--all] [--branches] [<rev>|<A..B>|<A...B>|^<rev>]... [--] [<path>...]";
+    let mut opts = LogOptions {
+        color: io::stdout().is_terminal(),
+        ..Default::default()
+    };
+
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        // "--flag=value" 와 "--flag value" 둘 다 받는다
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (arg, None),
+        };
+        let mut value = || inline.or_else(|| iter.next().copied()).ok_or(usage);
+        let count = |value: &str| value.parse::<usize>().map_err(|_| usage.to_string());
+
+        match flag {
+            "-n" | "--max-count" => opts.max_count = Some(count(value()?)?),
+            "--skip" => opts.skip = count(value()?)?,
+            "--author" => opts.author = Some(value()?.to_string()),
+            "--committer" => opts.committer = Some(value()?.to_string()),
+            "--grep" => opts.grep.push(value()?.to_string()),
+            "-i" | "--regexp-ignore-case" => opts.ignore_case = true,
+            "--since" | "--after" => opts.since = Some(value()?.to_string()),
+            "--until" | "--before" => opts.until = Some(value()?.to_string()),
+            "--trailer" => {
+                let filter = value()?;
+                opts.trailer = Some(match filter.split_once('=') {
+                    Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                    None => (filter.to_string(), None),
+                });
+            }
+            "--show-signature" => opts.show_signature = true,
+            "--graph" => opts.graph = true,
+            "--topo-order" => opts.topo_order = true,
+            "--oneline" => opts.format = Some(Format::Oneline),
+            "--pretty" | "--format" => {
+                opts.format = Some(Format::parse(value()?).map_err(|e| e.message().to_string())?)
+            }
+            "--date" => {
+                opts.date = DateFormat::parse(value()?).map_err(|e| e.message().to_string())?
+            }
+            "--all" => opts.all = true,
+            "--branches" => opts.branches = true,
+            "--no-color" => opts.color = false,
+            "--" => opts
+                .paths
+                .extend(iter.by_ref().map(|path| path.to_string())),
+            // -<N>
+            _ if arg
+                .strip_prefix('-')
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) =>
+            {
+                opts.max_count = Some(count(&arg[1..])?)
+            }
+            _ if arg.starts_with('-') => return Err(usage.to_string()),
+            // 리비전인지 경로인지는 log 에서 판단한다
+            _ => opts.revs.push(arg.to_string()),
+        }
+    }
+    Ok(opts)
+}
+
+// shortlog 의 -s, -e, -n (묶어서 -sne 도 가능) 외의 인자는 log 와 같이 해석한다
+fn parse_shortlog_args(args: &[&str]) -> Result<ShortlogOptions, String> {
+    let mut opts = ShortlogOptions::default();
+    let mut rest = Vec::new();
+    for &arg in args {
+        match arg {
+            "--summary" => opts.summary = true,
+            "--email" => opts.email = true,
+            "--numbered" => opts.numbered = true,
+            _ if arg.len() > 1
+                && arg.starts_with('-')
+                && arg[1..].chars().all(|c| matches!(c, 's' | 'e' | 'n')) =>
+            {
+                for c in arg[1..].chars() {
+                    match c {
+                        's' => opts.summary = true,
+                        'e' => opts.email = true,
+                        _ => opts.numbered = true,
+                    }
+                }
+            }
+            _ => rest.push(arg),
+        }
+    }
+    opts.log = parse_log_args(&rest)?;
+    Ok(opts)
+}
+
+// export 전용 옵션 외의 인자는 log 와 같이 해석한다. (옵션, -o 파일)
+fn parse_export_args<'a>(args: &[&'a str]) -> Result<(ExportOptions, Option<&'a str>), String> {
+    let usage = "입력 형식: export [--dot|--mermaid] [--objects] [--label <format>] [-o <file>] [<log 옵션>]";
+    let mut opts = ExportOptions::default();
+    let mut output = None;
+    let mut rest = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(&arg) = iter.next() {
+        match arg {
+            "--dot" => opts.format = ExportFormat::Dot,
+            "--mermaid" => opts.format = ExportFormat::Mermaid,
+            "--objects" => opts.objects = true,
+            "--label" => opts.label = Some(iter.next().ok_or(usage)?.to_string()),
+            "-o" | "--output" => output = Some(*iter.next().ok_or(usage)?),
+            _ => rest.push(arg),
+        }
+    }
+    opts.log = parse_log_args(&rest)?;
+    Ok((opts, output))
+}
+
+fn prompt(msg: &str) -> String {
+    print!("{}", msg);
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
+
+// user.name 이 없으면 커밋을 만들기 전에 지금 설정할지 물어본다
+fn ensure_identity() {
+    let Ok(repo) = Repository::open(".") else {
+        return;
+    };
+    if identity::is_configured(&repo) {
+        return;
+    }
+
+    println!("user.name 이 설정되어 있지 않습니다.");
+    if !prompt("지금 설정할까요? (y/N): ").eq_ignore_ascii_case("y") {
+        return;
+    }
+    let name = prompt("user.name: ");
+    let email = prompt("user.email: ");
+    let global = prompt("모든 저장소에 적용할까요? (y/N): ").eq_ignore_ascii_case("y");
+
+    match identity::set_identity(&repo, &name, &email, global) {
+        Ok(()) => println!("사용자 정보 설정 완료: {} <{}>", name, email),
+        Err(e) => println!("사용자 정보 설정 실패: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commit_args_rejects_stdin_file() {
+        assert!(parse_commit_args(&["-F", "-"]).is_err());
+        let opts = parse_commit_args(&["-F", "msg.txt"]).unwrap();
+        assert_eq!(opts.file.as_deref(), Some("msg.txt"));
+    }
+
+    #[test]
+    fn test_parse_log_args_count() {
+        let opts = parse_log_args(&["-3"]).unwrap();
+        assert_eq!(opts.max_count, Some(3));
+        assert!(opts.revs.is_empty());
+
+        // '-' 로 시작하지 않으면 숫자나 한글이어도 리비전/경로로 넘긴다
+        let opts = parse_log_args(&["2024", "가.txt"]).unwrap();
+        assert_eq!(opts.max_count, None);
+        assert_eq!(opts.revs, ["2024", "가.txt"]);
+
+        assert!(parse_log_args(&["-"]).is_err());
+        assert!(parse_log_args(&["-가"]).is_err());
+    }
+}
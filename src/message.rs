@@ -0,0 +1,399 @@
+use crate::identity;
+use git2::{Error, Repository, Status, StatusOptions};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cleanup {
+    // 주석 제거 + 공백 정리 (에디터로 작성한 메시지의 기본값)
+    Strip,
+    // 공백만 정리 (-m, -F 메시지의 기본값)
+    Whitespace,
+    // 아무것도 건드리지 않음
+    Verbatim,
+    // 가위 줄("# ---- >8 ----") 부터 아래를 잘라낸 뒤 strip (에디터로 작성할 때만)
+    Scissors,
+}
+
+impl Cleanup {
+    pub fn parse(mode: &str) -> Result<Cleanup, Error> {
+        match mode {
+            "strip" => Ok(Cleanup::Strip),
+            "whitespace" => Ok(Cleanup::Whitespace),
+            "verbatim" => Ok(Cleanup::Verbatim),
+            "scissors" => Ok(Cleanup::Scissors),
+            _ => Err(Error::from_str(&format!(
+                "알 수 없는 cleanup 모드: {}",
+                mode
+            ))),
+        }
+    }
+}
+
+pub fn comment_char(repo: &Repository) -> char {
+    repo.config()
+        .and_then(|cfg| cfg.get_string("core.commentChar"))
+        .ok()
+        .and_then(|s| s.chars().next())
+        .unwrap_or('#')
+}
+
+// git의 stripspace와 동일하게 동작:
+// 줄 끝 공백 제거, 연속된 빈 줄은 하나로, 앞뒤 빈 줄 제거, 마지막은 개행으로 끝남
+pub fn cleanup_message(message: &str, mode: Cleanup, comment: char) -> String {
+    if mode == Cleanup::Verbatim {
+        return message.to_string();
+    }
+
+    let scissors = format!(
+        "{} ------------------------ >8 ------------------------",
+        comment
+    );
+    let mut res = String::new();
+    let mut pending_blank = false;
+    for line in message.lines() {
+        if mode == Cleanup::Scissors && line == scissors {
+            break;
+        }
+        if matches!(mode, Cleanup::Strip | Cleanup::Scissors) && line.starts_with(comment) {
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            pending_blank = !res.is_empty();
+            continue;
+        }
+        if pending_blank {
+            res.push('\n');
+            pending_blank = false;
+        }
+        res.push_str(line);
+        res.push('\n');
+    }
+    res
+}
+
+// "Token: value" 또는 "Token #value" 형식의 trailer 줄인지 확인
+// 토큰은 영숫자와 '-' 로만 구성되며, 예외적으로 "BREAKING CHANGE" 를 허용한다
+pub fn parse_trailer_line(line: &str) -> Option<(&str, &str)> {
+    let pos = line.find([':', '#'])?;
+    let (token, value) = match line.as_bytes()[pos] {
+        b':' => (&line[..pos], line[pos + 1..].strip_prefix(' ')?),
+        _ => (line[..pos].strip_suffix(' ')?, &line[pos..]),
+    };
+    let valid_token = token == "BREAKING CHANGE"
+        || (!token.is_empty() && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    if !valid_token {
+        return None;
+    }
+    Some((token, value.trim()))
+}
+
+// 메시지 마지막 문단이 trailer 로만 이루어져 있으면 그 시작 줄 번호를 반환
+// 제목만 있는 메시지에는 trailer 가 없다
+fn trailer_block_start(lines: &[&str]) -> Option<usize> {
+    let start = lines.iter().rposition(|line| line.trim().is_empty())? + 1;
+    let block = &lines[start..];
+    let all_trailers = !block.is_empty()
+        && parse_trailer_line(block[0]).is_some()
+        && block
+            .iter()
+            .all(|line| parse_trailer_line(line).is_some() || line.starts_with([' ', '\t']));
+    all_trailers.then_some(start)
+}
+
+pub fn parse_trailers(message: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = message.trim_end().lines().collect();
+    let Some(start) = trailer_block_start(&lines) else {
+        return Vec::new();
+    };
+    let mut res: Vec<(String, String)> = Vec::new();
+    for line in &lines[start..] {
+        match parse_trailer_line(line) {
+            Some((token, value)) => res.push((token.to_string(), value.to_string())),
+            // 연속 줄은 이전 값에 이어 붙인다
+            None => {
+                if let Some(last) = res.last_mut() {
+                    last.1.push(' ');
+                    last.1.push_str(line.trim());
+                }
+            }
+        }
+    }
+    res
+}
+
+// "Key: value" 또는 "Key=value"
+pub fn parse_trailer_arg(arg: &str) -> Result<(String, String), Error> {
+    let (key, value) = arg
+        .split_once([':', '='])
+        .ok_or_else(|| Error::from_str(&format!("trailer 형식이 아님: {}", arg)))?;
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty() || parse_trailer_line(&format!("{}: {}", key, value)).is_none() {
+        return Err(Error::from_str(&format!("trailer 형식이 아님: {}", arg)));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+// 이미 같은 키(대소문자 무시)와 값이 있는 trailer 는 다시 추가하지 않는다
+pub fn append_trailers(message: &str, trailers: &[(String, String)]) -> String {
+    let mut existing = parse_trailers(message);
+    let mut res = message.trim_end().to_string();
+    let has_block = !existing.is_empty();
+
+    let mut added = false;
+    for (key, value) in trailers {
+        let duplicated = existing
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case(key) && v == value);
+        if duplicated {
+            continue;
+        }
+        if !has_block && !added {
+            res.push('\n');
+        }
+        res.push_str(&format!("\n{}: {}", key, value));
+        existing.push((key.clone(), value.clone()));
+        added = true;
+    }
+    res.push('\n');
+    res
+}
+
+pub fn signoff_trailer(repo: &Repository) -> Result<(String, String), Error> {
+    let sig = identity::committer_signature(repo)?;
+    Ok((
+        "Signed-off-by".to_string(),
+        format!(
+            "{} <{}>",
+            sig.name().unwrap_or(""),
+            sig.email().unwrap_or("")
+        ),
+    ))
+}
+
+// GIT_EDITOR > core.editor > VISUAL > EDITOR > vi 순서로 결정
+pub fn editor(repo: &Repository) -> String {
+    if let Ok(editor) = env::var("GIT_EDITOR") {
+        return editor;
+    }
+    if let Ok(editor) = repo.config().and_then(|cfg| cfg.get_string("core.editor")) {
+        return editor;
+    }
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string())
+}
+
+pub fn launch_editor(repo: &Repository, path: &Path) -> Result<(), Error> {
+    let editor = editor(repo);
+    // 에디터 설정에 인자가 포함될 수 있으므로 셸을 통해 실행한다
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(&editor)
+        .arg(path)
+        .status()
+        .map_err(|e| Error::from_str(&format!("에디터 실행 실패 '{}': {}", editor, e)))?;
+
+    if !status.success() {
+        return Err(Error::from_str(&format!("에디터 '{}' 비정상 종료", editor)));
+    }
+    Ok(())
+}
+
+pub fn commit_editmsg_path(repo: &Repository) -> PathBuf {
+    repo.path().join("COMMIT_EDITMSG")
+}
+
+// commit.template 설정이 있으면 해당 파일 내용을 읽어온다
+pub fn read_template(repo: &Repository) -> Result<Option<String>, Error> {
+    let path = match repo.config()?.get_path("commit.template") {
+        Ok(path) => path,
+        Err(_) => return Ok(None),
+    };
+    let path = match path.strip_prefix("~") {
+        Ok(rest) => env::var("HOME")
+            .map(|home| Path::new(&home).join(rest))
+            .unwrap_or(path.clone()),
+        Err(_) => path,
+    };
+    fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| Error::from_str(&format!("commit template 읽기 실패 {:?}: {}", path, e)))
+}
+
+// 에디터에 함께 보여줄 주석 처리된 상태 요약
+pub fn status_summary(repo: &Repository, comment: char) -> Result<String, Error> {
+    let mut lines = vec![
+        "Please enter the commit message for your changes. Lines starting".to_string(),
+        format!(
+            "with '{}' will be ignored, and an empty message aborts the commit.",
+            comment
+        ),
+        String::new(),
+    ];
+
+    match repo.head() {
+        Ok(head) if repo.head_detached()? => {
+            let id = head.target().map(|id| id.to_string()).unwrap_or_default();
+            lines.push(format!("HEAD detached at {}", &id[..id.len().min(7)]));
+        }
+        Ok(head) => lines.push(format!("On branch {}", head.shorthand().unwrap_or("HEAD"))),
+        Err(_) => lines.push("Initial commit".to_string()),
+    }
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut opts))?;
+
+    let mut staged = Vec::new();
+    let mut unstaged = Vec::new();
+    let mut untracked = Vec::new();
+    for entry in statuses.iter() {
+        let path = entry.path().unwrap_or("").to_string();
+        let status = entry.status();
+        if let Some(kind) = staged_kind(status) {
+            staged.push(format!("\t{:<12}{}", kind, path));
+        }
+        if let Some(kind) = unstaged_kind(status) {
+            unstaged.push(format!("\t{:<12}{}", kind, path));
+        }
+        if status.contains(Status::WT_NEW) {
+            untracked.push(format!("\t{}", path));
+        }
+    }
+
+    for (title, entries) in [
+        ("Changes to be committed:", staged),
+        ("Changes not staged for commit:", unstaged),
+        ("Untracked files:", untracked),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        lines.push(title.to_string());
+        lines.extend(entries);
+        lines.push(String::new());
+    }
+
+    let mut res = String::new();
+    for line in lines {
+        if line.is_empty() {
+            res.push_str(&format!("{}\n", comment));
+        } else {
+            res.push_str(&format!("{} {}\n", comment, line));
+        }
+    }
+    Ok(res)
+}
+
+fn staged_kind(status: Status) -> Option<&'static str> {
+    if status.contains(Status::INDEX_NEW) {
+        Some("new file:")
+    } else if status.contains(Status::INDEX_MODIFIED) {
+        Some("modified:")
+    } else if status.contains(Status::INDEX_DELETED) {
+        Some("deleted:")
+    } else if status.contains(Status::INDEX_RENAMED) {
+        Some("renamed:")
+    } else if status.contains(Status::INDEX_TYPECHANGE) {
+        Some("typechange:")
+    } else {
+        None
+    }
+}
+
+fn unstaged_kind(status: Status) -> Option<&'static str> {
+    if status.contains(Status::WT_MODIFIED) {
+        Some("modified:")
+    } else if status.contains(Status::WT_DELETED) {
+        Some("deleted:")
+    } else if status.contains(Status::WT_TYPECHANGE) {
+        Some("typechange:")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_message_strip() {
+        let msg = "\n\nsubject   \n\n\n\nbody line\n# comment\n\n";
+        assert_eq!(
+            cleanup_message(msg, Cleanup::Strip, '#'),
+            "subject\n\nbody line\n"
+        );
+    }
+
+    #[test]
+    fn test_cleanup_message_whitespace_keeps_comments() {
+        let msg = "subject\n# not a comment\n";
+        assert_eq!(
+            cleanup_message(msg, Cleanup::Whitespace, '#'),
+            "subject\n# not a comment\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_trailer_line() {
+        assert_eq!(
+            parse_trailer_line("Signed-off-by: A <a@b.c>"),
+            Some(("Signed-off-by", "A <a@b.c>"))
+        );
+        assert_eq!(
+            parse_trailer_line("BREAKING CHANGE: api removed"),
+            Some(("BREAKING CHANGE", "api removed"))
+        );
+        assert_eq!(parse_trailer_line("Refs #123"), Some(("Refs", "#123")));
+        assert_eq!(parse_trailer_line("not a trailer: x"), None);
+        assert_eq!(parse_trailer_line("Refs:missing-space"), None);
+    }
+
+    #[test]
+    fn test_parse_trailers() {
+        let msg = "subject\n\nbody\n\nSigned-off-by: A <a@b.c>\nRefs: #1\n  #2\n";
+        assert_eq!(
+            parse_trailers(msg),
+            vec![
+                ("Signed-off-by".to_string(), "A <a@b.c>".to_string()),
+                ("Refs".to_string(), "#1 #2".to_string()),
+            ]
+        );
+        assert!(parse_trailers("Refs: subject only\n").is_empty());
+        assert!(parse_trailers("subject\n\nbody: with colon\nplain text\n").is_empty());
+    }
+
+    #[test]
+    fn test_append_trailers() {
+        let trailers = vec![
+            ("Signed-off-by".to_string(), "A <a@b.c>".to_string()),
+            ("Refs".to_string(), "#1".to_string()),
+        ];
+        assert_eq!(
+            append_trailers("subject\n", &trailers),
+            "subject\n\nSigned-off-by: A <a@b.c>\nRefs: #1\n"
+        );
+        // 이미 있는 trailer 는 중복 추가하지 않고 기존 블록에 이어 붙인다
+        assert_eq!(
+            append_trailers("subject\n\nsigned-off-by: A <a@b.c>\n", &trailers),
+            "subject\n\nsigned-off-by: A <a@b.c>\nRefs: #1\n"
+        );
+    }
+
+    #[test]
+    fn test_cleanup_message_scissors() {
+        let msg = "subject\n# comment\n\n# ------------------------ >8 ------------------------\ndiff --git a b\n";
+        assert_eq!(cleanup_message(msg, Cleanup::Scissors, '#'), "subject\n");
+        assert_eq!(Cleanup::parse("scissors").unwrap(), Cleanup::Scissors);
+    }
+
+    #[test]
+    fn test_cleanup_message_only_comments_is_empty() {
+        assert_eq!(cleanup_message("# a\n# b\n", Cleanup::Strip, '#'), "");
+    }
+}
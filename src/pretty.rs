@@ -0,0 +1,365 @@
+use crate::date::{self, DateFormat};
+use git2::{Commit, Error, Signature};
+
+// log --pretty=<format> / --format=<format>
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    Oneline,
+    Short,
+    Medium,
+    Full,
+    Fuller,
+    // format:<...> 또는 % 가 들어간 문자열
+    Custom(String),
+}
+
+impl Format {
+    pub fn parse(input: &str) -> Result<Format, Error> {
+        if let Some(custom) = input
+            .strip_prefix("format:")
+            .or_else(|| input.strip_prefix("tformat:"))
+        {
+            return Ok(Format::Custom(custom.to_string()));
+        }
+        match input {
+            "oneline" => Ok(Format::Oneline),
+            "short" => Ok(Format::Short),
+            "medium" => Ok(Format::Medium),
+            "full" => Ok(Format::Full),
+            "fuller" => Ok(Format::Fuller),
+            _ if input.contains('%') => Ok(Format::Custom(input.to_string())),
+            _ => Err(Error::from_str(&format!(
+                "알 수 없는 출력 형식: {} (oneline, short, medium, full, fuller, format:<...>)",
+                input
+            ))),
+        }
+    }
+
+    // 여러 줄짜리 형식은 커밋 사이에 빈 줄을 넣는다
+    pub fn separated(&self) -> bool {
+        !matches!(self, Format::Oneline | Format::Custom(_))
+    }
+}
+
+// 커밋 하나를 형식에 맞게 줄 단위로 만든다
+pub struct Pretty<'a> {
+    pub format: &'a Format,
+    pub date: &'a DateFormat,
+    pub color: bool,
+}
+
+impl Pretty<'_> {
+    // decorations: "HEAD -> main", "tag: v1" 같은 (색이 입혀진) ref 이름들
+    pub fn lines(&self, commit: &Commit, decorations: &[String]) -> Vec<String> {
+        let hash = commit.id().to_string();
+        let decoration = self.decoration(decorations, " (", ")");
+        let message = String::from_utf8_lossy(commit.message_bytes());
+        let author = commit.author();
+        let committer = commit.committer();
+
+        let mut res = Vec::new();
+        match self.format {
+            Format::Oneline => {
+                res.push(format!(
+                    "{}{} {}",
+                    paint(self.color, "33", &hash[..7]),
+                    decoration,
+                    subject(commit)
+                ));
+                return res;
+            }
+            Format::Custom(custom) => {
+                let expanded = self.expand(commit, custom, decorations);
+                return expanded.split('\n').map(str::to_string).collect();
+            }
+            _ => {}
+        }
+
+        res.push(format!(
+            "{}{}",
+            paint(self.color, "33", &format!("commit {}", hash)),
+            decoration
+        ));
+        if commit.parent_count() > 1 {
+            let parents: Vec<String> = commit
+                .parent_ids()
+                .map(|id| id.to_string()[..7].to_string())
+                .collect();
+            res.push(format!("Merge: {}", parents.join(" ")));
+        }
+        match self.format {
+            Format::Short => res.push(format!("Author: {}", ident(&author))),
+            Format::Medium => {
+                res.push(format!("Author: {}", ident(&author)));
+                res.push(format!("Date:   {}", self.date(&author)));
+            }
+            Format::Full => {
+                res.push(format!("Author: {}", ident(&author)));
+                res.push(format!("Commit: {}", ident(&committer)));
+            }
+            _ => {
+                res.push(format!("Author:     {}", ident(&author)));
+                res.push(format!("AuthorDate: {}", self.date(&author)));
+                res.push(format!("Commit:     {}", ident(&committer)));
+                res.push(format!("CommitDate: {}", self.date(&committer)));
+            }
+        }
+
+        // 메시지가 비어 있어도 커밋은 출력한다
+        res.push(String::new());
+        if *self.format == Format::Short {
+            res.push(indent(&subject(commit)));
+        } else {
+            res.extend(message.trim_end().lines().map(indent));
+        }
+        res
+    }
+
+    fn date(&self, sig: &Signature) -> String {
+        date::format_date(&sig.when(), self.date)
+    }
+
+    fn decoration(&self, decorations: &[String], open: &str, close: &str) -> String {
+        if decorations.is_empty() {
+            return String::new();
+        }
+        format!(
+            "{}{}{}",
+            paint(self.color, "33", open),
+            decorations.join(&paint(self.color, "33", ", ")),
+            paint(self.color, "33", close)
+        )
+    }
+
+    // git 의 --format 자리표시자 중 자주 쓰는 것들
+    fn expand(&self, commit: &Commit, format: &str, decorations: &[String]) -> String {
+        let hash = commit.id().to_string();
+        let tree = commit.tree_id().to_string();
+        let message = String::from_utf8_lossy(commit.message_bytes());
+        let author = commit.author();
+        let committer = commit.committer();
+
+        let mut res = String::new();
+        let mut rest = format;
+        while let Some(pos) = rest.find('%') {
+            res.push_str(&rest[..pos]);
+            rest = &rest[pos + 1..];
+
+            // %a?, %c? 는 두 글자, %C(...) 와 %Cred 같은 색은 이름 길이만큼
+            let len = if rest.starts_with(['a', 'c']) && rest.is_char_boundary(2) {
+                2
+            } else if rest.starts_with("C(") {
+                rest.find(')').map_or(1, |end| end + 1)
+            } else if let Some(name) = ["Cred", "Cgreen", "Cblue", "Creset"]
+                .iter()
+                .find(|name| rest.starts_with(*name))
+            {
+                name.len()
+            } else {
+                rest.chars().next().map_or(0, char::len_utf8)
+            };
+            let placeholder = &rest[..len];
+
+            let sig = match placeholder.as_bytes().first() {
+                Some(b'a') => Some(&author),
+                Some(b'c') => Some(&committer),
+                _ => None,
+            };
+            let expanded = match (placeholder, sig) {
+                ("H", _) => hash.clone(),
+                ("h", _) => hash[..7].to_string(),
+                ("T", _) => tree.clone(),
+                ("t", _) => tree[..7].to_string(),
+                ("P", _) => join_ids(commit, 40),
+                ("p", _) => join_ids(commit, 7),
+                ("s", _) => subject(commit),
+                ("b", _) => body(&message),
+                ("B", _) => message.to_string(),
+                ("d", _) => self.decoration(decorations, " (", ")"),
+                ("D", _) => self.decoration(decorations, "", ""),
+                ("n", _) => "\n".to_string(),
+                ("%", _) => "%".to_string(),
+                ("Cred", _) => color_code(self.color, "31"),
+                ("Cgreen", _) => color_code(self.color, "32"),
+                ("Cblue", _) => color_code(self.color, "34"),
+                ("Creset", _) => color_code(self.color, ""),
+                (_, Some(sig)) => match &placeholder[1..] {
+                    "n" => String::from_utf8_lossy(sig.name_bytes()).to_string(),
+                    "e" => String::from_utf8_lossy(sig.email_bytes()).to_string(),
+                    "d" => self.date(sig),
+                    "D" => date::format_default(&sig.when()),
+                    "r" => date::format_relative(&sig.when()),
+                    "t" => sig.when().seconds().to_string(),
+                    "i" => date::format_date(&sig.when(), &DateFormat::Iso),
+                    "I" => date::format_date(&sig.when(), &DateFormat::IsoStrict),
+                    "s" => date::format_date(&sig.when(), &DateFormat::Short),
+                    _ => format!("%{}", placeholder),
+                },
+                _ if placeholder.starts_with("C(") => {
+                    let name = &placeholder[2..placeholder.len() - 1];
+                    color_code(self.color, color_by_name(name).unwrap_or(""))
+                }
+                // 모르는 자리표시자는 그대로 둔다
+                _ => format!("%{}", placeholder),
+            };
+            res.push_str(&expanded);
+            rest = &rest[len..];
+        }
+        res.push_str(rest);
+        res
+    }
+}
+
+// 첫 문단, 메시지가 비었거나 UTF-8 이 아니어도 빈 문자열/손실 변환으로 돌려준다
+pub fn subject(commit: &Commit) -> String {
+    commit
+        .summary_bytes()
+        .map(|summary| String::from_utf8_lossy(summary).to_string())
+        .unwrap_or_default()
+}
+
+fn body(message: &str) -> String {
+    match message.trim_start().split_once("\n\n") {
+        Some((_, body)) => body.trim_start_matches('\n').to_string(),
+        None => String::new(),
+    }
+}
+
+fn indent(line: &str) -> String {
+    if line.is_empty() {
+        String::new()
+    } else {
+        format!("    {}", line)
+    }
+}
+
+fn ident(sig: &Signature) -> String {
+    format!(
+        "{} <{}>",
+        String::from_utf8_lossy(sig.name_bytes()),
+        String::from_utf8_lossy(sig.email_bytes())
+    )
+}
+
+fn join_ids(commit: &Commit, len: usize) -> String {
+    commit
+        .parent_ids()
+        .map(|id| id.to_string()[..len].to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn color_by_name(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "reset" => "",
+        "bold" => "1",
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        "white" => "37",
+        _ => return None,
+    })
+}
+
+fn color_code(color: bool, code: &str) -> String {
+    if color {
+        format!("\x1b[{}m", code)
+    } else {
+        String::new()
+    }
+}
+
+pub fn paint(color: bool, code: &str, text: &str) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Time};
+
+    #[test]
+    fn test_pretty_formats() {
+        let dir = tempfile::TempDir::new().expect("failed to create temporary directory");
+        let repo = Repository::init(dir.path()).expect("failed to init repository");
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let author = Signature::new("Kim", "kim@example.com", &Time::new(1709263800, 540)).unwrap();
+        let committer =
+            Signature::new("Lee", "lee@example.com", &Time::new(1709263860, 0)).unwrap();
+        let oid = repo
+            .commit(
+                None,
+                &author,
+                &committer,
+                "subject line\n\nbody text\n",
+                &tree,
+                &[],
+            )
+            .unwrap();
+        let commit = repo.find_commit(oid).unwrap();
+        let empty = repo
+            .commit(None, &author, &committer, "", &tree, &[])
+            .unwrap();
+        let empty = repo.find_commit(empty).unwrap();
+
+        let lines = |format: &str, date: &str, commit: &Commit| {
+            Pretty {
+                format: &Format::parse(format).unwrap(),
+                date: &DateFormat::parse(date).unwrap(),
+                color: false,
+            }
+            .lines(commit, &["tag: v1".to_string()])
+        };
+        let hash = oid.to_string();
+
+        assert_eq!(
+            lines("oneline", "default", &commit),
+            [format!("{} (tag: v1) subject line", &hash[..7])]
+        );
+        assert_eq!(
+            lines("medium", "iso", &commit),
+            [
+                format!("commit {} (tag: v1)", hash),
+                "Author: Kim <kim@example.com>".to_string(),
+                "Date:   2024-03-01 12:30:00 +0900".to_string(),
+                String::new(),
+                "    subject line".to_string(),
+                String::new(),
+                "    body text".to_string(),
+            ]
+        );
+        assert_eq!(
+            lines("fuller", "short", &commit)[1..5],
+            [
+                "Author:     Kim <kim@example.com>",
+                "AuthorDate: 2024-03-01",
+                "Commit:     Lee <lee@example.com>",
+                "CommitDate: 2024-03-01",
+            ]
+        );
+        assert_eq!(
+            lines("format:%h|%an|%ce|%ad|%s|%b|%D|%x%%", "unix", &commit),
+            [
+                format!(
+                    "{}|Kim|lee@example.com|1709263800|subject line|body text",
+                    &hash[..7]
+                ),
+                "|tag: v1|%x%".to_string(),
+            ]
+        );
+
+        // 메시지가 빈 커밋도 빠지지 않는다
+        assert_eq!(lines("short", "default", &empty).len(), 4);
+        assert_eq!(lines("%s.", "default", &empty), ["."]);
+        assert!(Format::parse("fancy").is_err());
+    }
+}
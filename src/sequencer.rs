@@ -0,0 +1,103 @@
+use crate::commands::RevertOptions;
+use git2::{Error, Oid, Repository};
+use std::fs;
+use std::path::PathBuf;
+
+// 여러 커밋을 차례로 되돌리는 중간 상태. git 처럼 .git/sequencer 에 남겨서
+// 충돌로 멈춰도 revert --continue / --skip / --abort 로 이어갈 수 있게 한다
+//   head: 시작하기 전의 HEAD (--abort 로 돌아갈 곳)
+//   todo: 아직 되돌리지 않은 커밋 ("revert <oid> <subject>")
+//   opts: 되돌릴 때 쓴 옵션 ("key=value")
+pub struct Sequencer {
+    pub head: Oid,
+    pub todo: Vec<Oid>,
+    pub opts: RevertOptions,
+}
+
+fn dir(repo: &Repository) -> PathBuf {
+    repo.path().join("sequencer")
+}
+
+fn io_error(what: &str, e: std::io::Error) -> Error {
+    Error::from_str(&format!("sequencer {} 실패: {}", what, e))
+}
+
+impl Sequencer {
+    pub fn exists(repo: &Repository) -> bool {
+        dir(repo).is_dir()
+    }
+
+    pub fn load(repo: &Repository) -> Result<Sequencer, Error> {
+        let read =
+            |name: &str| fs::read_to_string(dir(repo).join(name)).map_err(|e| io_error("읽기", e));
+
+        let head = Oid::from_str(read("head")?.trim())?;
+        let mut todo = Vec::new();
+        for line in read("todo")?.lines() {
+            if let Some(oid) = line
+                .strip_prefix("revert ")
+                .and_then(|rest| rest.split(' ').next())
+            {
+                todo.push(Oid::from_str(oid)?);
+            }
+        }
+
+        let mut opts = RevertOptions::default();
+        for line in read("opts")?.lines() {
+            match line.split_once('=') {
+                Some(("signoff", value)) => opts.signoff = value == "true",
+                Some(("sign", value)) => opts.sign = Some(value == "true"),
+                Some(("mainline", value)) => opts.mainline = value.parse().ok(),
+                Some(("trailer", value)) => {
+                    if let Some((key, value)) = value.split_once(": ") {
+                        opts.trailers.push((key.to_string(), value.to_string()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(Sequencer { head, todo, opts })
+    }
+
+    pub fn save(&self, repo: &Repository) -> Result<(), Error> {
+        fs::create_dir_all(dir(repo)).map_err(|e| io_error("생성", e))?;
+        let write = |name: &str, content: String| {
+            fs::write(dir(repo).join(name), content).map_err(|e| io_error("저장", e))
+        };
+
+        write("head", format!("{}\n", self.head))?;
+
+        let mut todo = String::new();
+        for oid in &self.todo {
+            let commit = repo.find_commit(*oid)?;
+            todo.push_str(&format!(
+                "revert {} {}\n",
+                oid,
+                commit.summary().unwrap_or("")
+            ));
+        }
+        write("todo", todo)?;
+
+        let mut opts = String::new();
+        if self.opts.signoff {
+            opts.push_str("signoff=true\n");
+        }
+        if let Some(sign) = self.opts.sign {
+            opts.push_str(&format!("sign={}\n", sign));
+        }
+        if let Some(mainline) = self.opts.mainline {
+            opts.push_str(&format!("mainline={}\n", mainline));
+        }
+        for (key, value) in &self.opts.trailers {
+            opts.push_str(&format!("trailer={}: {}\n", key, value));
+        }
+        write("opts", opts)
+    }
+
+    pub fn remove(repo: &Repository) -> Result<(), Error> {
+        match fs::remove_dir_all(dir(repo)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error("삭제", e)),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use crate::identity;
+use git2::{Error, ErrorCode, Oid, Repository};
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+// 커밋/태그 서명 방식. gpg.format 설정으로 고른다.
+pub trait Signer {
+    // payload 에 대한 armored 서명 문자열 반환
+    fn sign(&self, payload: &[u8]) -> Result<String, Error>;
+}
+
+// ssh-keygen -Y sign
+pub struct SshSigner {
+    pub program: String,
+    pub key: String,
+}
+
+// gpg -bsau <key>
+pub struct GpgSigner {
+    pub program: String,
+    pub key: String,
+}
+
+impl Signer for SshSigner {
+    fn sign(&self, payload: &[u8]) -> Result<String, Error> {
+        // "key::ssh-ed25519 AAAA..." 처럼 공개키를 직접 적은 경우 ssh-agent 의 키로 서명
+        let literal = self.key.strip_prefix("key::");
+        let key_path = match literal {
+            Some(key) => write_temp(&std::env::temp_dir(), "signing_key", key.as_bytes())?,
+            None => PathBuf::from(&self.key),
+        };
+        let output = run(
+            Command::new(&self.program)
+                .args(["-Y", "sign", "-n", "git", "-f"])
+                .arg(&key_path),
+            payload,
+        );
+        if literal.is_some() {
+            let _ = fs::remove_file(&key_path);
+        }
+
+        let output = output?;
+        if !output.status.success() {
+            return Err(Error::from_str(&format!(
+                "ssh 서명 실패: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl Signer for GpgSigner {
+    fn sign(&self, payload: &[u8]) -> Result<String, Error> {
+        let output = run(
+            Command::new(&self.program).args(["--status-fd=2", "-bsau", &self.key]),
+            payload,
+        )?;
+        if !output.status.success() {
+            return Err(Error::from_str(&format!(
+                "gpg 서명 실패: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+pub fn signer(repo: &Repository) -> Result<Box<dyn Signer>, Error> {
+    let cfg = repo.config()?;
+    let format = cfg
+        .get_string("gpg.format")
+        .unwrap_or_else(|_| "openpgp".to_string());
+    let key = cfg
+        .get_path("user.signingKey")
+        .map(|path| path.to_string_lossy().to_string())
+        .ok();
+
+    match format.as_str() {
+        "ssh" => Ok(Box::new(SshSigner {
+            program: cfg
+                .get_string("gpg.ssh.program")
+                .unwrap_or_else(|_| "ssh-keygen".to_string()),
+            key: key
+                .ok_or_else(|| Error::from_str("ssh 서명에는 user.signingKey 설정이 필요함"))?,
+        })),
+        "openpgp" => {
+            // 키가 없으면 git 과 같이 committer 정보로 키를 찾는다
+            let key = match key {
+                Some(key) => key,
+                None => {
+                    let sig = identity::committer_signature(repo)?;
+                    format!(
+                        "{} <{}>",
+                        sig.name().unwrap_or(""),
+                        sig.email().unwrap_or("")
+                    )
+                }
+            };
+            Ok(Box::new(GpgSigner {
+                program: cfg
+                    .get_string("gpg.program")
+                    .unwrap_or_else(|_| "gpg".to_string()),
+                key,
+            }))
+        }
+        _ => Err(Error::from_str(&format!(
+            "지원하지 않는 gpg.format: {}",
+            format
+        ))),
+    }
+}
+
+// -S / --no-gpg-sign 이 없으면 commit.gpgSign 설정을 따른다
+pub fn should_sign(repo: &Repository, flag: Option<bool>, config_key: &str) -> bool {
+    flag.unwrap_or_else(|| {
+        repo.config()
+            .and_then(|cfg| cfg.get_bool(config_key))
+            .unwrap_or(false)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Unsigned,
+    Good(String),
+    Bad(String),
+    // 서명은 있지만 확인할 수 없음 (키 미등록, 도구 없음 등)
+    Unknown(String),
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verification::Unsigned => write!(f, "서명 없음"),
+            Verification::Good(signer) => write!(f, "Good signature from {}", signer),
+            Verification::Bad(reason) => write!(f, "BAD signature: {}", reason),
+            Verification::Unknown(reason) => write!(f, "서명 확인 불가: {}", reason),
+        }
+    }
+}
+
+pub fn verify_commit(repo: &Repository, oid: Oid) -> Result<Verification, Error> {
+    match repo.extract_signature(&oid, None) {
+        Ok((signature, signed_data)) => {
+            verify(repo, &signed_data, &String::from_utf8_lossy(&signature))
+        }
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(Verification::Unsigned),
+        Err(e) => Err(e),
+    }
+}
+
+// 태그 객체는 메시지 뒤에 서명이 그대로 붙어 있다
+pub fn verify_tag(repo: &Repository, oid: Oid) -> Result<Verification, Error> {
+    let odb = repo.odb()?;
+    let object = odb.read(oid)?;
+    let data = object.data();
+    match signature_start(data) {
+        Some(pos) => verify(repo, &data[..pos], &String::from_utf8_lossy(&data[pos..])),
+        None => Ok(Verification::Unsigned),
+    }
+}
+
+// 태그에 덧붙인 서명 블록의 시작 위치. 줄 처음에서 시작해 맨 끝까지 이어지는 블록만 서명으로 본다
+// (메시지 중간의 "-----BEGIN ..." 은 서명이 아님)
+pub fn signature_start(data: &[u8]) -> Option<usize> {
+    [
+        (
+            "-----BEGIN SSH SIGNATURE-----",
+            "-----END SSH SIGNATURE-----",
+        ),
+        (
+            "-----BEGIN PGP SIGNATURE-----",
+            "-----END PGP SIGNATURE-----",
+        ),
+    ]
+    .iter()
+    .filter_map(|(begin, end)| {
+        let pos = rfind(data, begin.as_bytes())?;
+        let at_line_start = pos == 0 || data[pos - 1] == b'\n';
+        let tail = String::from_utf8_lossy(&data[pos..]);
+        (at_line_start && tail.trim_end().ends_with(end)).then_some(pos)
+    })
+    .min()
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+pub fn verify(repo: &Repository, payload: &[u8], signature: &str) -> Result<Verification, Error> {
+    if signature.contains("-----BEGIN SSH SIGNATURE-----") {
+        verify_ssh(repo, payload, signature)
+    } else {
+        verify_gpg(repo, payload, signature)
+    }
+}
+
+fn verify_ssh(repo: &Repository, payload: &[u8], signature: &str) -> Result<Verification, Error> {
+    let cfg = repo.config()?;
+    let Ok(allowed) = cfg.get_path("gpg.ssh.allowedSignersFile") else {
+        return Ok(Verification::Unknown(
+            "gpg.ssh.allowedSignersFile 설정이 필요함".to_string(),
+        ));
+    };
+    let program = cfg
+        .get_string("gpg.ssh.program")
+        .unwrap_or_else(|_| "ssh-keygen".to_string());
+
+    let sig_path = write_temp(repo.path(), "ssh_signature", signature.as_bytes())?;
+    let res = (|| {
+        // 서명한 키가 allowed signers 의 어떤 principal 인지 찾는다
+        let found = run(
+            Command::new(&program)
+                .args(["-Y", "find-principals", "-f"])
+                .arg(&allowed)
+                .arg("-s")
+                .arg(&sig_path),
+            &[],
+        );
+        let found = match found {
+            Ok(output) if output.status.success() => output,
+            Ok(_) => {
+                return Ok(Verification::Unknown(
+                    "allowed signers 에 등록되지 않은 키".to_string(),
+                ));
+            }
+            Err(e) => return Ok(Verification::Unknown(e.message().to_string())),
+        };
+        let stdout = String::from_utf8_lossy(&found.stdout);
+        let principal = stdout.lines().next().unwrap_or("").trim().to_string();
+
+        let output = run(
+            Command::new(&program)
+                .args(["-Y", "verify", "-n", "git", "-f"])
+                .arg(&allowed)
+                .args(["-I", &principal, "-s"])
+                .arg(&sig_path),
+            payload,
+        )?;
+        if output.status.success() {
+            Ok(Verification::Good(principal))
+        } else {
+            let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            Ok(Verification::Bad(reason))
+        }
+    })();
+    let _ = fs::remove_file(&sig_path);
+    res
+}
+
+fn verify_gpg(repo: &Repository, payload: &[u8], signature: &str) -> Result<Verification, Error> {
+    let program = repo
+        .config()?
+        .get_string("gpg.program")
+        .unwrap_or_else(|_| "gpg".to_string());
+
+    let sig_path = write_temp(repo.path(), "gpg_signature", signature.as_bytes())?;
+    let output = run(
+        Command::new(&program)
+            .args(["--status-fd=1", "--verify"])
+            .arg(&sig_path)
+            .arg("-"),
+        payload,
+    );
+    let _ = fs::remove_file(&sig_path);
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => return Ok(Verification::Unknown(e.message().to_string())),
+    };
+
+    // [GNUPG:] GOODSIG <keyid> <user id>
+    let status = String::from_utf8_lossy(&output.stdout);
+    for line in status.lines() {
+        let Some(rest) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let mut parts = rest.splitn(3, ' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("GOODSIG"), Some(_), Some(uid)) => {
+                return Ok(Verification::Good(uid.to_string()));
+            }
+            (Some("BADSIG"), Some(_), Some(uid)) => return Ok(Verification::Bad(uid.to_string())),
+            (Some("NO_PUBKEY"), Some(key), _) => {
+                return Ok(Verification::Unknown(format!("공개키 없음 {}", key)));
+            }
+            _ => {}
+        }
+    }
+    Ok(Verification::Unknown(
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    ))
+}
+
+fn write_temp(dir: &Path, name: &str, data: &[u8]) -> Result<PathBuf, Error> {
+    let path = dir.join(format!("{}_{}", name, std::process::id()));
+    fs::write(&path, data).map_err(|e| Error::from_str(&format!("임시 파일 작성 실패: {}", e)))?;
+    Ok(path)
+}
+
+fn run(cmd: &mut Command, input: &[u8]) -> Result<Output, Error> {
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::from_str(&format!("{} 실행 실패: {}", program, e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input);
+    }
+    child
+        .wait_with_output()
+        .map_err(|e| Error::from_str(&format!("{} 실행 실패: {}", program, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{self, CommitOptions, TagOptions};
+    use crate::test_helpers::get_repo;
+    use serial_test::serial;
+
+    #[test]
+    fn test_signature_start() {
+        let sig = "-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----\n";
+        let msg = format!("msg -----BEGIN SSH SIGNATURE-----\n\n{}", sig);
+        assert_eq!(signature_start(msg.as_bytes()), Some(msg.len() - sig.len()));
+        assert_eq!(
+            signature_start(b"msg\n-----BEGIN PGP SIGNATURE-----\nnot signed\n"),
+            None
+        );
+        assert_eq!(signature_start(b"plain message\n"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_ssh_signed_commit_and_tag() {
+        let repo = get_repo();
+        let mut cfg = repo.config().unwrap();
+
+        let key = repo.path().join("test_signing_key");
+        let _ = fs::remove_file(&key);
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+            .arg(&key)
+            .status()
+            .expect("ssh-keygen not available");
+        assert!(status.success());
+        let public_key = fs::read_to_string(key.with_extension("pub")).unwrap();
+        let allowed = repo.path().join("allowed_signers");
+        fs::write(&allowed, format!("signer@example.com {}", public_key)).unwrap();
+
+        cfg.set_str("gpg.format", "ssh").unwrap();
+        cfg.set_str("user.signingKey", key.to_str().unwrap())
+            .unwrap();
+        cfg.set_str("gpg.ssh.allowedSignersFile", allowed.to_str().unwrap())
+            .unwrap();
+
+        fs::write("signed.txt", "signed").expect("failed to write file");
+        commands::git_add("signed.txt").expect("failed to add file");
+        let oid = commands::git_commit_with(&CommitOptions {
+            messages: vec!["signed commit".to_string()],
+            sign: Some(true),
+            ..Default::default()
+        })
+        .expect("failed to commit");
+        let oid: Oid = oid.parse().unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(oid));
+        assert_eq!(
+            verify_commit(&repo, oid).unwrap(),
+            Verification::Good("signer@example.com".to_string())
+        );
+        let parent = repo.find_commit(oid).unwrap().parent_id(0).unwrap();
+        assert_eq!(
+            verify_commit(&repo, parent).unwrap(),
+            Verification::Unsigned
+        );
+
+        let tag_opts = TagOptions {
+            message: Some("signed tag".to_string()),
+            sign: Some(true),
+            force: true,
+            ..Default::default()
+        };
+        commands::git_create_tag("v-signed", None, &tag_opts).expect("failed to tag");
+        assert_eq!(
+            commands::git_verify_tag("v-signed").unwrap(),
+            Verification::Good("signer@example.com".to_string())
+        );
+
+        // allowed signers 에 없는 키면 확인 불가
+        fs::write(&allowed, "").unwrap();
+        assert!(matches!(
+            verify_commit(&repo, oid).unwrap(),
+            Verification::Unknown(_)
+        ));
+
+        for key in [
+            "gpg.format",
+            "user.signingKey",
+            "gpg.ssh.allowedSignersFile",
+        ] {
+            cfg.remove(key).unwrap();
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::sequencer::Sequencer;
+use git2::{Error, Repository, RepositoryState};
+
+// 진행 중인 머지, revert, rebase 등이 있으면 무엇인지와 어떻게 끝내는지 알려준다
+pub fn in_progress(repo: &Repository) -> Option<&'static str> {
+    match repo.state() {
+        RepositoryState::Clean if Sequencer::exists(repo) => {
+            Some("진행 중인 revert 가 있음 (revert --continue / --skip / --abort)")
+        }
+        RepositoryState::Clean => None,
+        RepositoryState::Merge => Some("진행 중인 merge 가 있음 (merge --continue / --abort)"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => {
+            Some("진행 중인 revert 가 있음 (revert --continue / --skip / --abort)")
+        }
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+            Some("진행 중인 cherry-pick 이 있음 (git cherry-pick --continue / --abort)")
+        }
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => {
+            Some("진행 중인 rebase 가 있음 (git rebase --continue / --abort)")
+        }
+        RepositoryState::ApplyMailbox => Some("진행 중인 am 이 있음 (git am --continue / --abort)"),
+        RepositoryState::Bisect => Some("진행 중인 bisect 가 있음 (git bisect reset)"),
+    }
+}
+
+// 새 커밋을 만들거나 HEAD 를 옮기는 명령은 다른 작업이 끝날 때까지 막는다
+pub fn ensure_clean(repo: &Repository) -> Result<(), Error> {
+    match in_progress(repo) {
+        Some(message) => Err(Error::from_str(message)),
+        None => Ok(()),
+    }
+}
@@ -31,3 +31,106 @@ pub fn checkout(repo: &Repository, branch_name: &str) -> Result<(), git2::Error>
     repo.set_head(&format!("refs/heads/{}", branch_name))?;
     Ok(())
 }
+
+// 실행 가능한 훅 스크립트를 작성하고 경로를 반환
+pub fn write_hook(repo: &Repository, name: &str, body: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = repo.path().join("hooks");
+    fs::create_dir_all(&dir).expect("failed to create hooks directory");
+    let path = dir.join(name);
+    fs::write(&path, format!("#!/bin/sh\n{}\n", body)).expect("failed to write hook");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+        .expect("failed to set hook permissions");
+    path
+}
+
+// 작성자가 여럿이고 .mailmap 이 있는 별도 저장소를 만들고 그 안으로 이동
+pub fn authors_history() -> tempfile::TempDir {
+    let dir = tempfile::TempDir::new().expect("failed to create temporary directory");
+    let repo = Repository::init(dir.path()).expect("failed to init repository");
+    fs::write(
+        dir.path().join(".mailmap"),
+        "Kim <kim@example.com> <kim@old.example.com>\n",
+    )
+    .unwrap();
+
+    let commits = [
+        (
+            "Kim",
+            "kim@old.example.com",
+            1709251200,
+            "a.txt",
+            "1\n2\n",
+            "add a",
+        ),
+        (
+            "Lee",
+            "lee@example.com",
+            1709337600,
+            "b.txt",
+            "1\n",
+            "add b",
+        ),
+        (
+            "Kim",
+            "kim@example.com",
+            1709856000,
+            "a.txt",
+            "1\n3\n4\n",
+            "edit a",
+        ),
+    ];
+    let mut parent = None;
+    for (name, email, time, path, content, msg) in commits {
+        fs::write(dir.path().join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::new(name, email, &git2::Time::new(time, 0)).unwrap();
+        let parents: Vec<git2::Commit> = parent
+            .iter()
+            .map(|&p| repo.find_commit(p).unwrap())
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        parent = Some(
+            repo.commit(Some("HEAD"), &sig, &sig, msg, &tree, &parents)
+                .unwrap(),
+        );
+    }
+
+    std::env::set_current_dir(dir.path()).unwrap();
+    dir
+}
+
+// root <- main <- merge, root <- side(tag v1) <- merge 히스토리를 가진 별도 저장소로 이동
+// 날짜가 고정된 히스토리를 만들기 위해 공유 저장소 대신 사용한다
+pub fn merge_history() -> (tempfile::TempDir, [git2::Oid; 4]) {
+    let dir = tempfile::TempDir::new().expect("failed to create temporary directory");
+    let repo = Repository::init(dir.path()).expect("failed to init repository");
+    repo.set_head("refs/heads/main").unwrap();
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let commit = |refname: &str, msg: &str, time: i64, parents: &[git2::Oid]| {
+        let sig =
+            git2::Signature::new("agent", "agent@example.com", &git2::Time::new(time, 0)).unwrap();
+        let parents: Vec<git2::Commit> = parents
+            .iter()
+            .map(|&p| repo.find_commit(p).unwrap())
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some(refname), &sig, &sig, msg, &tree, &parents)
+            .unwrap()
+    };
+    let root = commit("refs/heads/main", "root", 1, &[]);
+    let side = commit("refs/heads/side", "side", 3, &[root]);
+    let main = commit("refs/heads/main", "main", 2, &[root]);
+    let merge = commit("refs/heads/main", "merge", 4, &[main, side]);
+    repo.tag_lightweight("v1", &repo.find_object(side, None).unwrap(), false)
+        .unwrap();
+
+    std::env::set_current_dir(dir.path()).unwrap();
+    (dir, [root, side, main, merge])
+}